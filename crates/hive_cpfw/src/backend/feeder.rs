@@ -0,0 +1,3 @@
+pub trait FeederBackend {
+    fn dispense_ml(&mut self, syrup_ml: u16);
+}
@@ -1,8 +1,14 @@
-use super::{fan::FanBackend, heater::HeaterBackend, led::LedBackend};
+use super::{
+    entrance::EntranceReducerBackend, fan::FanBackend, feeder::FeederBackend,
+    heater::HeaterBackend, led::LedBackend, vent::VentFlapBackend,
+};
 
 pub struct MockHeater;
 pub struct MockFan;
 pub struct MockLed;
+pub struct MockEntranceReducer;
+pub struct MockVentFlap;
+pub struct MockFeeder;
 
 impl HeaterBackend for MockHeater {
     fn set_celsius(&mut self, _celsius: i16) {}
@@ -15,3 +21,15 @@ impl FanBackend for MockFan {
 impl LedBackend for MockLed {
     fn set_lux(&mut self, _lux: u32) {}
 }
+
+impl EntranceReducerBackend for MockEntranceReducer {
+    fn set_open_pct(&mut self, _open_pct: u8) {}
+}
+
+impl VentFlapBackend for MockVentFlap {
+    fn set_open_pct(&mut self, _open_pct: u8) {}
+}
+
+impl FeederBackend for MockFeeder {
+    fn dispense_ml(&mut self, _syrup_ml: u16) {}
+}
@@ -1,4 +1,7 @@
 pub mod heater;
 pub mod fan;
 pub mod led;
+pub mod entrance;
+pub mod vent;
+pub mod feeder;
 pub mod mock;
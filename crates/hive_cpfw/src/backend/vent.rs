@@ -0,0 +1,3 @@
+pub trait VentFlapBackend {
+    fn set_open_pct(&mut self, open_pct: u8);
+}
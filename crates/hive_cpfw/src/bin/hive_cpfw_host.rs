@@ -1,6 +1,7 @@
 use hive_cpfw::enforcer::Enforcer;
 use hive_cpfw::integration::{bundle_to_cp_policy, shard_bridge};
 use hive_cpfw::state::BandStateSnapshot;
+use hive_shard_runtime::limits::{ActuationCaps, EntranceEnvelope, ShardLimits};
 
 fn main() {
     let dummy_bundle = bee_biostretched_policy::bundle::HivePolicyBundle::new(
@@ -25,14 +26,34 @@ fn main() {
         },
     );
 
-    let cp_policy = bundle_to_cp_policy(&dummy_bundle);
+    let caps = ActuationCaps {
+        heater_max_celsius: 36,
+        fan_max_duty_pct: 60,
+        led_max_lux: 800,
+        zones: Default::default(),
+        entrance: Some(EntranceEnvelope {
+            min_open_pct: 0,
+            flight_min_open_pct: 20,
+            flight_start_hour: 6,
+            flight_end_hour: 20,
+            utc_offset_min: 0,
+        }),
+        vent_flap: None,
+        feeder: None,
+    };
+    let limits = ShardLimits {
+        max_spikes_per_period: 10_000,
+        max_inferences_per_minute: 60,
+        max_joules_per_inference_mj: 50,
+        max_actuator_duty_cycle_pct: 60,
+        max_delta_t_c_per_hour: 1,
+        max_delta_db_per_hour: 3,
+        max_fan_delta_pct_per_hour: 20,
+    };
+    let cp_policy = bundle_to_cp_policy(&dummy_bundle, &caps, &limits);
     let enforcer = Enforcer::new(cp_policy);
 
-    let frame = hive_shard_runtime::actuator::ActuatorCommandFrame {
-        heater_celsius: 2,
-        fan_duty_pct: 10,
-        led_lux: 0,
-    };
+    let frame = hive_shard_runtime::actuator::ActuatorCommandFrame::brood_and_entrance(2, 10, 0);
     let snapshots = shard_bridge::frame_to_requests("host-hive", &frame, 0);
     let band_state = BandStateSnapshot {
         band: "green".into(),
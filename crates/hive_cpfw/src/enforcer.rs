@@ -26,7 +26,6 @@ impl Enforcer {
             };
         }
 
-        let mut magnitude = req.magnitude;
         let reason;
 
         if req.magnitude < 0 {
@@ -39,6 +38,17 @@ impl Enforcer {
             };
         }
 
+        let (min, max) = self.policy.envelope(&req);
+        let magnitude = req.magnitude.clamp(min, max.max(min));
+        if magnitude != req.magnitude {
+            return ActuationDecision {
+                request: req,
+                kind: DecisionKind::Modify,
+                reason: "outside_envelope".into(),
+                modified_magnitude: magnitude,
+            };
+        }
+
         reason = "within_limits".into();
         ActuationDecision {
             request: req,
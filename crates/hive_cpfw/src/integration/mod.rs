@@ -1,2 +1,4 @@
 pub mod shard_bridge;
 pub mod policy_bridge;
+
+pub use policy_bridge::bundle_to_cp_policy;
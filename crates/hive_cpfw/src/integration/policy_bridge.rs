@@ -1,14 +1,44 @@
 use bee_biostretched_policy::HivePolicyBundle;
+use hive_shard_runtime::limits::{self, ActuationCaps, ShardLimits};
 
-use crate::policy::CpPolicy;
+use crate::policy::{CpPolicy, EntranceEnvelope};
 
-pub fn bundle_to_cp_policy(bundle: &HivePolicyBundle) -> CpPolicy {
+/// Firewall policy for one hive. The heater ceiling follows the bundle's
+/// site baseline; every other envelope is taken from the caps and limits
+/// the hive's shard enforces, so both layers agree on flight hours and
+/// on which actuators may move at all.
+pub fn bundle_to_cp_policy(
+    bundle: &HivePolicyBundle,
+    caps: &ActuationCaps,
+    limits: &ShardLimits,
+) -> CpPolicy {
     let p = &bundle.policy;
     CpPolicy {
-        max_heater_celsius: p.baseline.baseline_brood_temp_c + 2,
-        max_fan_duty_pct: 60,
-        max_led_lux: 800,
-        max_delta_t_c_per_hour: 1,
-        max_delta_db_per_hour: 3,
+        max_heater_celsius: (p.baseline.baseline_brood_temp_c + 2)
+            .min(caps.heater_max_celsius as i32),
+        max_fan_duty_pct: caps.fan_max_duty_pct as i32,
+        max_led_lux: caps.led_max_lux.min(i32::MAX as u32) as i32,
+        max_delta_t_c_per_hour: limits.max_delta_t_c_per_hour as i32,
+        max_delta_db_per_hour: limits.max_delta_db_per_hour as i32,
+        entrance: caps.entrance.as_ref().map(entrance_envelope),
+        max_vent_open_pct: caps.vent_flap.as_ref().map_or(0, |f| f.max_open_pct as i32),
+        max_feeder_ml_per_request: caps
+            .feeder
+            .as_ref()
+            .map_or(0, |f| f.max_ml_per_period as i32),
+        max_feeder_ml_per_day: caps
+            .feeder
+            .as_ref()
+            .map_or(0, |f| f.max_ml_per_day.min(i32::MAX as u32) as i32),
+    }
+}
+
+fn entrance_envelope(e: &limits::EntranceEnvelope) -> EntranceEnvelope {
+    EntranceEnvelope {
+        min_open_pct: e.min_open_pct as i32,
+        flight_min_open_pct: e.flight_min_open_pct as i32,
+        flight_start_hour: e.flight_start_hour,
+        flight_end_hour: e.flight_end_hour,
+        utc_offset_min: e.utc_offset_min,
     }
 }
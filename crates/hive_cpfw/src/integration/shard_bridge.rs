@@ -1,41 +1,37 @@
-use hive_shard_runtime::actuator::ActuatorCommandFrame;
+use hive_shard_runtime::actuator::{ActuatorCommand, ActuatorCommandFrame};
 use hive_shard_runtime::sensor::SensorSnapshot;
 
 use crate::request::{ActuationRequest, ActuationType};
 
+/// One request per channel, located at the channel's zone. Heater, fan,
+/// LED and feeder channels at zero are skipped; a reducer or flap at zero
+/// is a request to close it.
 pub fn frame_to_requests(
     hive_id: &str,
     frame: &ActuatorCommandFrame,
     snapshot_ms: u64,
 ) -> Vec<ActuationRequest> {
     let mut out = Vec::new();
-    if frame.heater_celsius != 0 {
+    for channel in frame.channels.iter() {
+        let (actuator, magnitude, positional) = match channel.command {
+            ActuatorCommand::Heater { celsius } => (ActuationType::Heater, celsius as i32, false),
+            ActuatorCommand::Fan { duty_pct } => (ActuationType::Fan, duty_pct as i32, false),
+            ActuatorCommand::Led { lux } => (ActuationType::Led, lux as i32, false),
+            ActuatorCommand::EntranceReducer { open_pct } => {
+                (ActuationType::EntranceReducer, open_pct as i32, true)
+            }
+            ActuatorCommand::VentFlap { open_pct } => (ActuationType::VentFlap, open_pct as i32, true),
+            ActuatorCommand::Feeder { syrup_ml } => (ActuationType::Feeder, syrup_ml as i32, false),
+        };
+        if magnitude == 0 && !positional {
+            continue;
+        }
         out.push(ActuationRequest {
             hive_id: hive_id.to_string(),
-            actuator: ActuationType::Heater,
-            magnitude: frame.heater_celsius as i32,
+            actuator,
+            magnitude,
             duration_ms: 1000,
-            location: "brood".into(),
-            requested_at_ms: snapshot_ms,
-        });
-    }
-    if frame.fan_duty_pct != 0 {
-        out.push(ActuationRequest {
-            hive_id: hive_id.to_string(),
-            actuator: ActuationType::Fan,
-            magnitude: frame.fan_duty_pct as i32,
-            duration_ms: 1000,
-            location: "brood".into(),
-            requested_at_ms: snapshot_ms,
-        });
-    }
-    if frame.led_lux != 0 {
-        out.push(ActuationRequest {
-            hive_id: hive_id.to_string(),
-            actuator: ActuationType::Led,
-            magnitude: frame.led_lux as i32,
-            duration_ms: 1000,
-            location: "entrance".into(),
+            location: channel.zone.as_str().into(),
             requested_at_ms: snapshot_ms,
         });
     }
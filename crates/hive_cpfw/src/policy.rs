@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 
-use crate::request::ActuationRequest;
+use crate::request::{ActuationRequest, ActuationType};
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum DecisionKind {
@@ -16,3 +16,76 @@ pub struct ActuationDecision {
     pub reason: String,
     pub modified_magnitude: i32,
 }
+
+const MS_PER_MINUTE: i64 = 60_000;
+const MS_PER_HOUR: i64 = 3_600_000;
+const MS_PER_DAY: i64 = 86_400_000;
+
+/// Actuation envelope the enforcer holds every request to.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CpPolicy {
+    pub max_heater_celsius: i32,
+    pub max_fan_duty_pct: i32,
+    pub max_led_lux: i32,
+    pub max_delta_t_c_per_hour: i32,
+    pub max_delta_db_per_hour: i32,
+    /// Without an envelope the reducer is held fully open.
+    pub entrance: Option<EntranceEnvelope>,
+    pub max_vent_open_pct: i32,
+    pub max_feeder_ml_per_request: i32,
+    /// Checked per request here; the shard keeps the rolling daily total.
+    pub max_feeder_ml_per_day: i32,
+}
+
+/// The entrance is never closed past `flight_min_open_pct` while bees fly.
+/// Mirrors the shard's `EntranceEnvelope`, local hours included.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct EntranceEnvelope {
+    pub min_open_pct: i32,
+    pub flight_min_open_pct: i32,
+    /// Local hour flight starts, inclusive.
+    pub flight_start_hour: u8,
+    /// Local hour flight ends, exclusive; may wrap past midnight.
+    pub flight_end_hour: u8,
+    pub utc_offset_min: i16,
+}
+
+impl EntranceEnvelope {
+    /// Whether bees may be flying at `at_ms`, Unix milliseconds.
+    pub fn in_flight_hours(&self, at_ms: u64) -> bool {
+        let local_ms = (at_ms as i64 + self.utc_offset_min as i64 * MS_PER_MINUTE)
+            .rem_euclid(MS_PER_DAY);
+        let hour = (local_ms / MS_PER_HOUR) as u8;
+        if self.flight_start_hour <= self.flight_end_hour {
+            (self.flight_start_hour..self.flight_end_hour).contains(&hour)
+        } else {
+            hour >= self.flight_start_hour || hour < self.flight_end_hour
+        }
+    }
+}
+
+impl CpPolicy {
+    /// Allowed `(min, max)` magnitude for `req`.
+    pub fn envelope(&self, req: &ActuationRequest) -> (i32, i32) {
+        match req.actuator {
+            ActuationType::Heater => (0, self.max_heater_celsius),
+            ActuationType::Fan => (0, self.max_fan_duty_pct),
+            ActuationType::Led => (0, self.max_led_lux),
+            ActuationType::EntranceReducer => {
+                let min = match &self.entrance {
+                    Some(e) if e.in_flight_hours(req.requested_at_ms) => {
+                        e.min_open_pct.max(e.flight_min_open_pct)
+                    }
+                    Some(e) => e.min_open_pct,
+                    None => 100,
+                };
+                (min.min(100), 100)
+            }
+            ActuationType::VentFlap => (0, self.max_vent_open_pct),
+            ActuationType::Feeder => (
+                0,
+                self.max_feeder_ml_per_request.min(self.max_feeder_ml_per_day),
+            ),
+        }
+    }
+}
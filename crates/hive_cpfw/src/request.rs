@@ -5,6 +5,12 @@ pub enum ActuationType {
     Heater,
     Fan,
     Led,
+    /// Magnitude is the opening in percent.
+    EntranceReducer,
+    /// Magnitude is the opening in percent.
+    VentFlap,
+    /// Magnitude is the syrup volume in ml.
+    Feeder,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
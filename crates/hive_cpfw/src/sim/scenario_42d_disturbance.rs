@@ -1,5 +1,5 @@
 use crate::enforcer::Enforcer;
-use crate::policy::CpPolicy;
+use crate::policy::{CpPolicy, EntranceEnvelope};
 use crate::request::{ActuationRequest, ActuationType};
 use crate::state::BandStateSnapshot;
 
@@ -10,6 +10,16 @@ pub fn run_simulation() {
         max_led_lux: 1000,
         max_delta_t_c_per_hour: 1,
         max_delta_db_per_hour: 3,
+        entrance: Some(EntranceEnvelope {
+            min_open_pct: 0,
+            flight_min_open_pct: 20,
+            flight_start_hour: 6,
+            flight_end_hour: 20,
+            utc_offset_min: 0,
+        }),
+        max_vent_open_pct: 80,
+        max_feeder_ml_per_request: 250,
+        max_feeder_ml_per_day: 2000,
     };
     let enforcer = Enforcer::new(policy);
     let band_state = BandStateSnapshot {
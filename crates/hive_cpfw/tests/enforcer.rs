@@ -0,0 +1,188 @@
+use hive_cpfw::policy::{CpPolicy, EntranceEnvelope};
+use hive_cpfw::request::{ActuationRequest, ActuationType};
+use hive_cpfw::state::BandStateSnapshot;
+use hive_cpfw::Enforcer;
+
+/// 2024-06-01 00:00 UTC.
+const MIDNIGHT_MS: u64 = 1_717_200_000_000;
+const MS_PER_MINUTE: u64 = 60_000;
+
+fn utc(hour: u64, minute: u64) -> u64 {
+    MIDNIGHT_MS + (hour * 60 + minute) * MS_PER_MINUTE
+}
+
+fn policy() -> CpPolicy {
+    CpPolicy {
+        max_heater_celsius: 36,
+        max_fan_duty_pct: 80,
+        max_led_lux: 200,
+        max_delta_t_c_per_hour: 2,
+        max_delta_db_per_hour: 6,
+        entrance: None,
+        max_vent_open_pct: 60,
+        max_feeder_ml_per_request: 50,
+        max_feeder_ml_per_day: 30,
+    }
+}
+
+/// Flight from 20:00 to 04:00 local time, two hours ahead of UTC.
+fn night_flight() -> EntranceEnvelope {
+    EntranceEnvelope {
+        min_open_pct: 10,
+        flight_min_open_pct: 40,
+        flight_start_hour: 20,
+        flight_end_hour: 4,
+        utc_offset_min: 120,
+    }
+}
+
+fn band(band: &str) -> BandStateSnapshot {
+    BandStateSnapshot {
+        band: band.into(),
+        bioload: "normal".into(),
+    }
+}
+
+/// Reason and magnitude the enforcer decides on under a green band.
+fn decide(
+    policy: &CpPolicy,
+    actuator: ActuationType,
+    magnitude: i32,
+    requested_at_ms: u64,
+) -> (String, i32) {
+    let req = ActuationRequest {
+        hive_id: "hive-1".into(),
+        actuator,
+        magnitude,
+        duration_ms: 60_000,
+        location: "apiary".into(),
+        requested_at_ms,
+    };
+    let decision = Enforcer::new(policy.clone()).decide(req, &band("green"));
+    (decision.reason, decision.modified_magnitude)
+}
+
+fn allowed(magnitude: i32) -> (String, i32) {
+    ("within_limits".into(), magnitude)
+}
+
+fn clamped(magnitude: i32) -> (String, i32) {
+    ("outside_envelope".into(), magnitude)
+}
+
+#[test]
+fn capped_actuators_clamp_to_their_maximum() {
+    let policy = policy();
+    let noon = utc(12, 0);
+    let caps = [
+        (ActuationType::Heater, 36),
+        (ActuationType::Fan, 80),
+        (ActuationType::Led, 200),
+        (ActuationType::VentFlap, 60),
+    ];
+
+    for (actuator, max) in caps {
+        assert_eq!(decide(&policy, actuator.clone(), max, noon), allowed(max));
+        assert_eq!(decide(&policy, actuator.clone(), 0, noon), allowed(0));
+        assert_eq!(decide(&policy, actuator, max + 1, noon), clamped(max));
+    }
+}
+
+#[test]
+fn feeder_is_held_to_the_smaller_of_request_and_day_limits() {
+    let mut policy = policy();
+    let noon = utc(12, 0);
+
+    assert_eq!(
+        decide(&policy, ActuationType::Feeder, 30, noon),
+        allowed(30)
+    );
+    assert_eq!(
+        decide(&policy, ActuationType::Feeder, 45, noon),
+        clamped(30)
+    );
+
+    policy.max_feeder_ml_per_day = 500;
+    assert_eq!(
+        decide(&policy, ActuationType::Feeder, 45, noon),
+        allowed(45)
+    );
+    assert_eq!(
+        decide(&policy, ActuationType::Feeder, 80, noon),
+        clamped(50)
+    );
+}
+
+#[test]
+fn reducer_without_an_envelope_is_held_open() {
+    let policy = policy();
+
+    assert_eq!(
+        decide(&policy, ActuationType::EntranceReducer, 100, utc(3, 0)),
+        allowed(100)
+    );
+    assert_eq!(
+        decide(&policy, ActuationType::EntranceReducer, 20, utc(3, 0)),
+        clamped(100)
+    );
+}
+
+#[test]
+fn reducer_flight_floor_wraps_past_local_midnight() {
+    let mut policy = policy();
+    policy.entrance = Some(night_flight());
+    let reducer = |at_ms| decide(&policy, ActuationType::EntranceReducer, 0, at_ms);
+
+    // 17:59 UTC is 19:59 local, before flight.
+    assert_eq!(reducer(utc(17, 59)), clamped(10));
+    assert_eq!(reducer(utc(18, 0)), clamped(40));
+    // 23:30 UTC is 01:30 local the next day, still flying.
+    assert_eq!(reducer(utc(23, 30)), clamped(40));
+    assert_eq!(reducer(utc(1, 59)), clamped(40));
+    assert_eq!(reducer(utc(2, 0)), clamped(10));
+    assert_eq!(
+        decide(&policy, ActuationType::EntranceReducer, 55, utc(23, 30)),
+        allowed(55)
+    );
+}
+
+#[test]
+fn reducer_offset_west_of_utc_lands_on_the_previous_local_day() {
+    let mut policy = policy();
+    policy.entrance = Some(EntranceEnvelope {
+        flight_start_hour: 6,
+        flight_end_hour: 20,
+        utc_offset_min: -300,
+        ..night_flight()
+    });
+    let reducer = |at_ms| decide(&policy, ActuationType::EntranceReducer, 0, at_ms);
+
+    // 00:30 UTC is 19:30 local the day before.
+    assert_eq!(reducer(utc(0, 30)), clamped(40));
+    assert_eq!(reducer(utc(1, 0)), clamped(10));
+    assert_eq!(reducer(utc(11, 0)), clamped(40));
+}
+
+#[test]
+fn red_band_and_negative_magnitudes_are_denied() {
+    let enforcer = Enforcer::new(policy());
+    let req = |magnitude| ActuationRequest {
+        hive_id: "hive-1".into(),
+        actuator: ActuationType::Fan,
+        magnitude,
+        duration_ms: 60_000,
+        location: "apiary".into(),
+        requested_at_ms: utc(12, 0),
+    };
+
+    let red = enforcer.decide(req(40), &band("red"));
+    assert_eq!(
+        (red.reason.as_str(), red.modified_magnitude),
+        ("band_red", 0)
+    );
+    let negative = enforcer.decide(req(-1), &band("green"));
+    assert_eq!(
+        (negative.reason.as_str(), negative.modified_magnitude),
+        ("negative_magnitude_denied", 0)
+    );
+}
@@ -0,0 +1,205 @@
+//! On-shard acoustic feature pipeline.
+//!
+//! PCM frames from the hive microphone are reduced to a broadband level, a
+//! fixed-point band spectrum (one Goertzel filter per band) and the surplus
+//! over the site's baseline, which is what `SensorSnapshot::acoustic_surplus_db`
+//! carries. Known colony-state signatures are flagged as events once they
+//! persist for `sustain_frames` consecutive frames.
+
+use heapless::Vec;
+use serde::{Deserialize, Serialize};
+
+/// Band centre frequencies of the spectrum, in Hz.
+pub const ACOUSTIC_BANDS_HZ: [u16; 12] = [
+    100, 150, 200, 250, 300, 350, 400, 450, 500, 600, 800, 1000,
+];
+pub const ACOUSTIC_BAND_COUNT: usize = ACOUSTIC_BANDS_HZ.len();
+
+/// Bands making up the 200–500 Hz pre-swarm buzz range.
+const BUZZ_BANDS: core::ops::RangeInclusive<usize> = 2..=8;
+/// Bands where queen tooting and quacking fundamentals sit (350–500 Hz).
+const PIPING_BANDS: core::ops::RangeInclusive<usize> = 5..=8;
+
+const Q14: i64 = 1 << 14;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AcousticConfig {
+    pub sample_rate_hz: u32,
+    /// Site baseline from the policy bundle's `SiteBaseline`.
+    pub baseline_acoustic_db: i16,
+    /// Added to the digital full-scale level to get calibrated dB.
+    pub mic_offset_db: i16,
+    /// 200–500 Hz energy over baseline that counts as pre-swarm buzz.
+    pub buzz_margin_db: i16,
+    /// Peak over its neighbouring bands that counts as a piping tone.
+    pub piping_tonal_margin_db: i16,
+    /// Every band over baseline by this much counts as a queenless roar.
+    pub roar_margin_db: i16,
+    pub sustain_frames: u8,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum AcousticSignature {
+    QueenPiping,
+    QueenlessRoar,
+    PreSwarmBuzz,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct AcousticEvent {
+    pub signature: AcousticSignature,
+    /// Dominant band centre when the event fired.
+    pub peak_hz: u16,
+    pub level_db: i16,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct AcousticFeatures {
+    pub level_db: i16,
+    pub surplus_db: i16,
+    pub band_db: [i16; ACOUSTIC_BAND_COUNT],
+    pub peak_hz: u16,
+    /// Signatures that started this frame.
+    pub events: Vec<AcousticEvent, 3>,
+}
+
+pub struct AcousticAnalyzer {
+    config: AcousticConfig,
+    /// `2 cos(ω)` per band, Q14.
+    coeffs: [i64; ACOUSTIC_BAND_COUNT],
+    streaks: [u8; 3],
+}
+
+impl AcousticAnalyzer {
+    pub fn new(config: AcousticConfig) -> Self {
+        let fs = config.sample_rate_hz.max(1) as f32;
+        let mut coeffs = [0i64; ACOUSTIC_BAND_COUNT];
+        for (coeff, hz) in coeffs.iter_mut().zip(ACOUSTIC_BANDS_HZ.iter()) {
+            let omega = 2.0 * core::f32::consts::PI * *hz as f32 / fs;
+            *coeff = (2.0 * cos(omega) * Q14 as f32) as i64;
+        }
+        Self {
+            config,
+            coeffs,
+            streaks: [0; 3],
+        }
+    }
+
+    pub fn process(&mut self, frame: &[i16]) -> AcousticFeatures {
+        let mut out = AcousticFeatures::default();
+        if frame.is_empty() {
+            return out;
+        }
+        let n = frame.len() as u64;
+        let offset = self.config.mic_offset_db;
+
+        let energy: u64 = frame.iter().map(|s| (*s as i64 * *s as i64) as u64).sum();
+        out.level_db = db10(energy / n).saturating_add(offset);
+        out.surplus_db = out.level_db.saturating_sub(self.config.baseline_acoustic_db);
+
+        // |X(ω)|² of a sine of amplitude A is (A·N/2)², so normalising by N²/2
+        // gives its mean power A²/2, comparable with the broadband level.
+        let norm_db = db10(n * n / 2);
+        let mut peak = 0;
+        for (band, coeff) in self.coeffs.iter().enumerate() {
+            let power = goertzel_power(frame, *coeff);
+            out.band_db[band] = db10(power).saturating_sub(norm_db).saturating_add(offset);
+            if out.band_db[band] > out.band_db[peak] {
+                peak = band;
+            }
+        }
+        out.peak_hz = ACOUSTIC_BANDS_HZ[peak];
+
+        let detected = self.classify(&out.band_db, peak);
+        for (i, signature) in [
+            AcousticSignature::QueenPiping,
+            AcousticSignature::QueenlessRoar,
+            AcousticSignature::PreSwarmBuzz,
+        ]
+        .iter()
+        .enumerate()
+        {
+            if !detected[i] {
+                self.streaks[i] = 0;
+                continue;
+            }
+            self.streaks[i] = self.streaks[i].saturating_add(1);
+            if self.streaks[i] == self.config.sustain_frames.max(1) {
+                let _ = out.events.push(AcousticEvent {
+                    signature: *signature,
+                    peak_hz: out.peak_hz,
+                    level_db: out.level_db,
+                });
+            }
+        }
+        out
+    }
+
+    /// Returns `[piping, roar, buzz]` detections for one frame.
+    fn classify(&self, band_db: &[i16; ACOUSTIC_BAND_COUNT], peak: usize) -> [bool; 3] {
+        let c = &self.config;
+        let baseline = c.baseline_acoustic_db;
+
+        let piping = PIPING_BANDS.contains(&peak) && {
+            let left = band_db[peak - 1];
+            let right = band_db.get(peak + 1).copied().unwrap_or(i16::MIN);
+            band_db[peak].saturating_sub(left.max(right)) >= c.piping_tonal_margin_db
+        };
+
+        let roar = band_db
+            .iter()
+            .all(|db| db.saturating_sub(baseline) >= c.roar_margin_db);
+
+        let buzz_sum: i32 = band_db[BUZZ_BANDS].iter().map(|db| *db as i32).sum();
+        let buzz_mean = buzz_sum / BUZZ_BANDS.count() as i32;
+        let buzz = buzz_mean - baseline as i32 >= c.buzz_margin_db as i32;
+
+        [piping, roar && !piping, buzz && !roar && !piping]
+    }
+}
+
+/// Goertzel filter output power for one frame; `coeff` is `2 cos(ω)` in Q14.
+fn goertzel_power(frame: &[i16], coeff: i64) -> u64 {
+    let (mut s1, mut s2) = (0i64, 0i64);
+    for sample in frame {
+        let s0 = *sample as i64 + ((coeff * s1) >> 14) - s2;
+        s2 = s1;
+        s1 = s0;
+    }
+    let power = s1 * s1 + s2 * s2 - ((coeff * s1) >> 14) * s2;
+    power.max(0) as u64
+}
+
+/// `10·log10(x)` in whole dB, from an integer log2 with a linear mantissa.
+fn db10(x: u64) -> i16 {
+    if x == 0 {
+        return 0;
+    }
+    let int = 63 - x.leading_zeros() as u64;
+    let frac = if int >= 8 {
+        (x >> (int - 8)) & 0xFF
+    } else {
+        (x << (8 - int)) & 0xFF
+    };
+    let log2_q8 = (int << 8) | frac;
+    // 10·log10(2) ≈ 3.0103 ≈ 771/256.
+    ((log2_q8 * 771) >> 16) as i16
+}
+
+/// Cosine for coefficient setup: an eighth-order Taylor series after range
+/// reduction, accurate to ~2.5e-5 over any angle.
+fn cos(x: f32) -> f32 {
+    use core::f32::consts::{FRAC_PI_2, PI};
+    let tau = 2.0 * PI;
+    let mut x = x - tau * ((x / tau) as i32) as f32;
+    if x < 0.0 {
+        x = -x;
+    }
+    if x > PI {
+        x = tau - x;
+    }
+    let (x, sign) = if x > FRAC_PI_2 { (PI - x, -1.0) } else { (x, 1.0) };
+    let x2 = x * x;
+    let series = 1.0 - x2 / 2.0 * (1.0 - x2 / 12.0 * (1.0 - x2 / 30.0 * (1.0 - x2 / 56.0)));
+    sign * series
+}
@@ -1,18 +1,212 @@
+use heapless::Vec;
 use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+/// Upper bound on channels in one `ActuatorCommandFrame`: one for every
+/// zone and actuator kind, so `set` never runs out of room.
+pub const MAX_ACTUATOR_CHANNELS: usize = ActuatorZone::ALL.len() * ActuatorKind::ALL.len();
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum ActuatorZone {
+    Brood,
+    Entrance,
+    HoneySuper,
+}
+
+impl ActuatorZone {
+    pub const ALL: [ActuatorZone; 3] = [
+        ActuatorZone::Brood,
+        ActuatorZone::Entrance,
+        ActuatorZone::HoneySuper,
+    ];
+
+    /// Location name used in actuation requests and audit logs.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ActuatorZone::Brood => "brood",
+            ActuatorZone::Entrance => "entrance",
+            ActuatorZone::HoneySuper => "honey_super",
+        }
+    }
+
+    pub(crate) fn index(&self) -> usize {
+        match self {
+            ActuatorZone::Brood => 0,
+            ActuatorZone::Entrance => 1,
+            ActuatorZone::HoneySuper => 2,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum ActuatorCommand {
+    Heater { celsius: i16 },
+    Fan { duty_pct: u8 },
+    Led { lux: u32 },
+    /// Motorized entrance reducer; 0 is fully closed.
+    EntranceReducer { open_pct: u8 },
+    /// Top ventilation flap; 0 is fully closed.
+    VentFlap { open_pct: u8 },
+    /// Syrup to pump into the feeder this period.
+    Feeder { syrup_ml: u16 },
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum ActuatorKind {
+    Heater,
+    Fan,
+    Led,
+    EntranceReducer,
+    VentFlap,
+    Feeder,
+}
+
+impl ActuatorKind {
+    pub const ALL: [ActuatorKind; 6] = [
+        ActuatorKind::Heater,
+        ActuatorKind::Fan,
+        ActuatorKind::Led,
+        ActuatorKind::EntranceReducer,
+        ActuatorKind::VentFlap,
+        ActuatorKind::Feeder,
+    ];
+}
+
+impl ActuatorCommand {
+    pub fn kind(&self) -> ActuatorKind {
+        match self {
+            ActuatorCommand::Heater { .. } => ActuatorKind::Heater,
+            ActuatorCommand::Fan { .. } => ActuatorKind::Fan,
+            ActuatorCommand::Led { .. } => ActuatorKind::Led,
+            ActuatorCommand::EntranceReducer { .. } => ActuatorKind::EntranceReducer,
+            ActuatorCommand::VentFlap { .. } => ActuatorKind::VentFlap,
+            ActuatorCommand::Feeder { .. } => ActuatorKind::Feeder,
+        }
+    }
+
+    /// Whether `other` drives the same kind of actuator.
+    pub fn same_kind(&self, other: &ActuatorCommand) -> bool {
+        self.kind() == other.kind()
+    }
+
+    /// Whether the actuator draws power this period. Reducers and flaps
+    /// only move, so they never count as on.
+    pub fn is_energized(&self) -> bool {
+        match *self {
+            ActuatorCommand::Heater { celsius } => celsius != 0,
+            ActuatorCommand::Fan { duty_pct } => duty_pct != 0,
+            ActuatorCommand::Led { lux } => lux != 0,
+            ActuatorCommand::Feeder { syrup_ml } => syrup_ml != 0,
+            ActuatorCommand::EntranceReducer { .. } | ActuatorCommand::VentFlap { .. } => false,
+        }
+    }
+
+    /// Switches a powered actuator off; reducers and flaps are unchanged.
+    pub fn switch_off(&mut self) {
+        match self {
+            ActuatorCommand::Heater { celsius } => *celsius = 0,
+            ActuatorCommand::Fan { duty_pct } => *duty_pct = 0,
+            ActuatorCommand::Led { lux } => *lux = 0,
+            ActuatorCommand::Feeder { syrup_ml } => *syrup_ml = 0,
+            ActuatorCommand::EntranceReducer { .. } | ActuatorCommand::VentFlap { .. } => {}
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ActuatorChannel {
+    pub zone: ActuatorZone,
+    pub command: ActuatorCommand,
+}
+
+/// Actuator outputs for one control period, one channel per zone and
+/// actuator kind. Actuators without a channel are off; reducers and flaps
+/// without one hold their position.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub struct ActuatorCommandFrame {
-    pub heater_celsius: i16,
-    pub fan_duty_pct: u8,
-    pub led_lux: u32,
+    pub channels: Vec<ActuatorChannel, MAX_ACTUATOR_CHANNELS>,
 }
 
 impl ActuatorCommandFrame {
     pub fn observation_only() -> Self {
-        Self {
-            heater_celsius: 0,
-            fan_duty_pct: 0,
-            led_lux: 0,
+        Self::default()
+    }
+
+    /// Single-body hive layout: heater and fan in the brood zone, LED at
+    /// the entrance.
+    pub fn brood_and_entrance(heater_celsius: i16, fan_duty_pct: u8, led_lux: u32) -> Self {
+        let mut frame = Self::default();
+        frame.set(ActuatorZone::Brood, ActuatorCommand::Heater { celsius: heater_celsius });
+        frame.set(ActuatorZone::Brood, ActuatorCommand::Fan { duty_pct: fan_duty_pct });
+        frame.set(ActuatorZone::Entrance, ActuatorCommand::Led { lux: led_lux });
+        frame
+    }
+
+    /// Replaces the channel of the same zone and kind, or adds one. Returns
+    /// `false` if the frame is full, which only happens when channels were
+    /// pushed directly with zones and kinds repeated.
+    pub fn set(&mut self, zone: ActuatorZone, command: ActuatorCommand) -> bool {
+        if let Some(channel) = self
+            .channels
+            .iter_mut()
+            .find(|c| c.zone == zone && c.command.same_kind(&command))
+        {
+            channel.command = command;
+            return true;
         }
+        self.channels.push(ActuatorChannel { zone, command }).is_ok()
+    }
+
+    pub fn heater_celsius(&self, zone: ActuatorZone) -> i16 {
+        self.channels
+            .iter()
+            .find_map(|c| match c.command {
+                ActuatorCommand::Heater { celsius } if c.zone == zone => Some(celsius),
+                _ => None,
+            })
+            .unwrap_or(0)
+    }
+
+    pub fn fan_duty_pct(&self, zone: ActuatorZone) -> u8 {
+        self.channels
+            .iter()
+            .find_map(|c| match c.command {
+                ActuatorCommand::Fan { duty_pct } if c.zone == zone => Some(duty_pct),
+                _ => None,
+            })
+            .unwrap_or(0)
+    }
+
+    pub fn led_lux(&self, zone: ActuatorZone) -> u32 {
+        self.channels
+            .iter()
+            .find_map(|c| match c.command {
+                ActuatorCommand::Led { lux } if c.zone == zone => Some(lux),
+                _ => None,
+            })
+            .unwrap_or(0)
+    }
+
+    pub fn entrance_open_pct(&self, zone: ActuatorZone) -> Option<u8> {
+        self.channels.iter().find_map(|c| match c.command {
+            ActuatorCommand::EntranceReducer { open_pct } if c.zone == zone => Some(open_pct),
+            _ => None,
+        })
+    }
+
+    pub fn vent_open_pct(&self, zone: ActuatorZone) -> Option<u8> {
+        self.channels.iter().find_map(|c| match c.command {
+            ActuatorCommand::VentFlap { open_pct } if c.zone == zone => Some(open_pct),
+            _ => None,
+        })
+    }
+
+    pub fn feeder_syrup_ml(&self, zone: ActuatorZone) -> u16 {
+        self.channels
+            .iter()
+            .find_map(|c| match c.command {
+                ActuatorCommand::Feeder { syrup_ml } if c.zone == zone => Some(syrup_ml),
+                _ => None,
+            })
+            .unwrap_or(0)
     }
 }
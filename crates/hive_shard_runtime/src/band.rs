@@ -14,31 +14,42 @@ impl BandState {
         matches!(self, BandState::Red)
     }
 
+    /// Instantaneous classification of `snapshot`, with hysteresis relative
+    /// to `self`: a level is only left once every reading is back inside its
+    /// bounds by the configured margin.
     pub fn evaluate(
         &self,
         snapshot: &SensorSnapshot,
         thresholds: &BandThresholds,
     ) -> BandState {
-        if snapshot.brood_temp_c < thresholds.red_min_temp_c
-            || snapshot.brood_temp_c > thresholds.red_max_temp_c
-            || snapshot.brood_humidity_pct < thresholds.red_min_humidity_pct
-            || snapshot.brood_humidity_pct > thresholds.red_max_humidity_pct
-            || snapshot.acoustic_surplus_db > thresholds.red_max_acoustic_surplus_db
-            || snapshot.daily_mortality_pct > thresholds.red_max_daily_mortality_pct
-        {
+        let sticky_red = self.is_red();
+        let sticky_yellow = !matches!(self, BandState::Green);
+
+        if thresholds.breaches(snapshot, BandState::Red, sticky_red) {
             BandState::Red
-        } else if snapshot.brood_temp_c < thresholds.yellow_min_temp_c
-            || snapshot.brood_temp_c > thresholds.yellow_max_temp_c
-            || snapshot.brood_humidity_pct < thresholds.yellow_min_humidity_pct
-            || snapshot.brood_humidity_pct > thresholds.yellow_max_humidity_pct
-            || snapshot.acoustic_surplus_db > thresholds.yellow_max_acoustic_surplus_db
-            || snapshot.daily_mortality_pct > thresholds.yellow_max_daily_mortality_pct
-        {
+        } else if thresholds.breaches(snapshot, BandState::Yellow, sticky_yellow) {
             BandState::Yellow
         } else {
             BandState::Green
         }
     }
+
+    /// The more severe of `self` and `floor`.
+    pub fn escalate(self, floor: BandState) -> BandState {
+        if floor.level() > self.level() {
+            floor
+        } else {
+            self
+        }
+    }
+
+    fn level(&self) -> u8 {
+        match self {
+            BandState::Green => 0,
+            BandState::Yellow => 1,
+            BandState::Red => 2,
+        }
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -55,6 +66,116 @@ pub struct BandThresholds {
     pub red_max_acoustic_surplus_db: i16,
     pub yellow_max_daily_mortality_pct: u8,
     pub red_max_daily_mortality_pct: u8,
+    pub yellow_max_brood_gradient_c: i16,
+    pub red_max_brood_gradient_c: i16,
+    pub hysteresis_temp_c: i16,
+    pub hysteresis_humidity_pct: u8,
+    pub hysteresis_acoustic_db: i16,
+    pub hysteresis_mortality_pct: u8,
+    /// De-escalation needs `debounce_n` of the last `debounce_m` samples
+    /// (at most `BAND_DEBOUNCE_MAX_SAMPLES`) at the lower level.
+    pub debounce_n: u8,
+    pub debounce_m: u8,
+}
+
+impl BandThresholds {
+    /// Whether any reading falls outside the bounds of `level` (red or
+    /// yellow), shrunk inward by the hysteresis margins when `sticky` is set.
+    fn breaches(&self, snapshot: &SensorSnapshot, level: BandState, sticky: bool) -> bool {
+        let (temp_c, humidity_pct, max_acoustic_db, max_mortality_pct, max_gradient_c) =
+            if level.is_red() {
+                (
+                    (self.red_min_temp_c, self.red_max_temp_c),
+                    (self.red_min_humidity_pct, self.red_max_humidity_pct),
+                    self.red_max_acoustic_surplus_db,
+                    self.red_max_daily_mortality_pct,
+                    self.red_max_brood_gradient_c,
+                )
+            } else {
+                (
+                    (self.yellow_min_temp_c, self.yellow_max_temp_c),
+                    (self.yellow_min_humidity_pct, self.yellow_max_humidity_pct),
+                    self.yellow_max_acoustic_surplus_db,
+                    self.yellow_max_daily_mortality_pct,
+                    self.yellow_max_brood_gradient_c,
+                )
+            };
+        let (temp_margin, humidity_margin, acoustic_margin, mortality_margin) = if sticky {
+            (
+                self.hysteresis_temp_c,
+                self.hysteresis_humidity_pct,
+                self.hysteresis_acoustic_db,
+                self.hysteresis_mortality_pct,
+            )
+        } else {
+            (0, 0, 0, 0)
+        };
+
+        snapshot.brood_temp_c < temp_c.0.saturating_add(temp_margin)
+            || snapshot.brood_temp_c > temp_c.1.saturating_sub(temp_margin)
+            || snapshot.brood_humidity_pct < humidity_pct.0.saturating_add(humidity_margin)
+            || snapshot.brood_humidity_pct > humidity_pct.1.saturating_sub(humidity_margin)
+            || snapshot.acoustic_surplus_db > max_acoustic_db.saturating_sub(acoustic_margin)
+            || snapshot.daily_mortality_pct > max_mortality_pct.saturating_sub(mortality_margin)
+            || snapshot.brood_gradient_c > max_gradient_c.saturating_sub(temp_margin)
+    }
+}
+
+/// Upper bound on `BandThresholds::debounce_m`.
+pub const BAND_DEBOUNCE_MAX_SAMPLES: u8 = 32;
+
+/// N-of-M debounce over raw `BandState::evaluate` results.
+///
+/// Escalation to red is immediate; escalation to yellow and every
+/// de-escalation require `debounce_n` of the last `debounce_m` samples.
+#[derive(Clone, Debug, Default)]
+pub struct BandDebouncer {
+    at_least_yellow: u32,
+    red: u32,
+    samples: u8,
+}
+
+impl BandDebouncer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn update(
+        &mut self,
+        current: BandState,
+        raw: BandState,
+        thresholds: &BandThresholds,
+    ) -> BandState {
+        self.at_least_yellow = (self.at_least_yellow << 1) | (raw.level() >= 1) as u32;
+        self.red = (self.red << 1) | raw.is_red() as u32;
+        self.samples = self.samples.saturating_add(1).min(BAND_DEBOUNCE_MAX_SAMPLES);
+
+        if raw.is_red() {
+            return BandState::Red;
+        }
+
+        let m = thresholds.debounce_m.clamp(1, BAND_DEBOUNCE_MAX_SAMPLES).min(self.samples);
+        let n = thresholds.debounce_n.clamp(1, m) as u32;
+        let mask = if m >= 32 { u32::MAX } else { (1u32 << m) - 1 };
+        let yellow_or_worse = (self.at_least_yellow & mask).count_ones();
+        let red = (self.red & mask).count_ones();
+        let green = m as u32 - yellow_or_worse;
+
+        match current {
+            BandState::Red if m as u32 - red >= n => {
+                if green >= n {
+                    BandState::Green
+                } else {
+                    BandState::Yellow
+                }
+            }
+            BandState::Red => BandState::Red,
+            BandState::Yellow if green >= n => BandState::Green,
+            BandState::Yellow => BandState::Yellow,
+            BandState::Green if yellow_or_worse >= n => BandState::Yellow,
+            BandState::Green => BandState::Green,
+        }
+    }
 }
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
@@ -89,4 +210,21 @@ impl BioloadState {
     pub fn is_critical(&self) -> bool {
         matches!(self, BioloadState::Critical)
     }
+
+    /// The more severe of `self` and `floor`.
+    pub fn escalate(self, floor: BioloadState) -> BioloadState {
+        if floor.level() > self.level() {
+            floor
+        } else {
+            self
+        }
+    }
+
+    fn level(&self) -> u8 {
+        match self {
+            BioloadState::Nominal => 0,
+            BioloadState::Elevated => 1,
+            BioloadState::Critical => 2,
+        }
+    }
 }
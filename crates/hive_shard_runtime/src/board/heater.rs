@@ -0,0 +1,69 @@
+use embedded_hal::digital::OutputPin;
+
+use crate::timebase::TickCounter;
+
+/// Time-proportioned relay driver for resistive heaters.
+///
+/// The heater setpoint is turned into a duty cycle by a proportional band
+/// around the measured brood temperature, and the relay is switched on for
+/// that fraction of every `window_ticks` window. A setpoint of 0 means off.
+pub struct HeaterRelay<P> {
+    pin: P,
+    window_ticks: u32,
+    proportional_band_c: i16,
+    on: bool,
+}
+
+impl<P: OutputPin> HeaterRelay<P> {
+    pub fn new(pin: P, window_ticks: u32, proportional_band_c: i16) -> Self {
+        Self {
+            pin,
+            window_ticks: window_ticks.max(1),
+            proportional_band_c: proportional_band_c.max(1),
+            on: false,
+        }
+    }
+
+    /// Duty in percent for reaching `setpoint_c` from `measured_c`.
+    pub fn duty_pct(&self, setpoint_c: i16, measured_c: i16) -> u8 {
+        if setpoint_c == 0 {
+            return 0;
+        }
+        let error = setpoint_c as i32 - measured_c as i32;
+        (error * 100 / self.proportional_band_c as i32).clamp(0, 100) as u8
+    }
+
+    pub fn update(
+        &mut self,
+        setpoint_c: i16,
+        measured_c: i16,
+        tick: TickCounter,
+    ) -> Result<(), P::Error> {
+        let duty = self.duty_pct(setpoint_c, measured_c) as u64;
+        let window = self.window_ticks as u64;
+        let on_ticks = (duty * window).div_ceil(100);
+        self.set(tick.ticks() % window < on_ticks)
+    }
+
+    pub fn off(&mut self) -> Result<(), P::Error> {
+        self.set(false)
+    }
+
+    pub fn is_on(&self) -> bool {
+        self.on
+    }
+
+    pub fn release(self) -> P {
+        self.pin
+    }
+
+    fn set(&mut self, on: bool) -> Result<(), P::Error> {
+        if on {
+            self.pin.set_high()?;
+        } else {
+            self.pin.set_low()?;
+        }
+        self.on = on;
+        Ok(())
+    }
+}
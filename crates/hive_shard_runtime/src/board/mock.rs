@@ -1,15 +1,109 @@
-use embedded_hal::digital::v2::OutputPin;
+use core::convert::Infallible;
 
-pub struct MockHeaterPin;
+use embedded_hal::digital::{self, OutputPin};
+use embedded_hal::pwm::{self, SetDutyCycle};
+use heapless::Vec;
 
-impl OutputPin for MockHeaterPin {
-    type Error = core::convert::Infallible;
+use super::ActuatorBoard;
+use crate::actuator::ActuatorCommandFrame;
+use crate::sensor::SensorSnapshot;
+use crate::timebase::TickCounter;
 
+/// Frames retained by `RecordingBoard`; older frames are dropped.
+pub const MOCK_BOARD_HISTORY: usize = 64;
+
+/// Output pin that remembers its level.
+#[derive(Debug, Default)]
+pub struct MockPin {
+    pub high: bool,
+    pub toggles: u32,
+}
+
+impl digital::ErrorType for MockPin {
+    type Error = Infallible;
+}
+
+impl OutputPin for MockPin {
     fn set_low(&mut self) -> Result<(), Self::Error> {
+        if self.high {
+            self.toggles += 1;
+        }
+        self.high = false;
         Ok(())
     }
 
     fn set_high(&mut self) -> Result<(), Self::Error> {
+        if !self.high {
+            self.toggles += 1;
+        }
+        self.high = true;
+        Ok(())
+    }
+}
+
+/// PWM channel that remembers its duty cycle.
+#[derive(Debug)]
+pub struct MockPwm {
+    pub max_duty: u16,
+    pub duty: u16,
+}
+
+impl MockPwm {
+    pub fn new(max_duty: u16) -> Self {
+        Self { max_duty, duty: 0 }
+    }
+}
+
+impl pwm::ErrorType for MockPwm {
+    type Error = Infallible;
+}
+
+impl SetDutyCycle for MockPwm {
+    fn max_duty_cycle(&self) -> u16 {
+        self.max_duty
+    }
+
+    fn set_duty_cycle(&mut self, duty: u16) -> Result<(), Self::Error> {
+        self.duty = duty.min(self.max_duty);
+        Ok(())
+    }
+}
+
+/// Board that records every applied frame, for host-side tests.
+#[derive(Debug, Default)]
+pub struct RecordingBoard {
+    pub frames: Vec<(u64, ActuatorCommandFrame), MOCK_BOARD_HISTORY>,
+    pub all_off_calls: u32,
+}
+
+impl RecordingBoard {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn last(&self) -> Option<&ActuatorCommandFrame> {
+        self.frames.last().map(|(_, frame)| frame)
+    }
+}
+
+impl ActuatorBoard for RecordingBoard {
+    type Error = Infallible;
+
+    fn apply(
+        &mut self,
+        frame: &ActuatorCommandFrame,
+        _sensors: &SensorSnapshot,
+        tick: TickCounter,
+    ) -> Result<(), Self::Error> {
+        if self.frames.is_full() {
+            self.frames.remove(0);
+        }
+        let _ = self.frames.push((tick.ticks(), frame.clone()));
+        Ok(())
+    }
+
+    fn all_off(&mut self) -> Result<(), Self::Error> {
+        self.all_off_calls += 1;
         Ok(())
     }
 }
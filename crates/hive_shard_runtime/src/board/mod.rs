@@ -1,3 +1,122 @@
+//! Board abstraction mapping `ActuatorCommandFrame`s onto embedded-hal 1.0
+//! peripherals.
+
+pub mod heater;
 pub mod stm32;
 pub mod ra;
 pub mod mock;
+
+use embedded_hal::digital::OutputPin;
+use embedded_hal::pwm::SetDutyCycle;
+
+use crate::actuator::{ActuatorCommandFrame, ActuatorZone};
+use crate::sensor::SensorSnapshot;
+use crate::timebase::TickCounter;
+
+use self::heater::HeaterRelay;
+
+/// Drives the physical actuators of a shard once per control period.
+pub trait ActuatorBoard {
+    type Error;
+
+    fn apply(
+        &mut self,
+        frame: &ActuatorCommandFrame,
+        sensors: &SensorSnapshot,
+        tick: TickCounter,
+    ) -> Result<(), Self::Error>;
+
+    /// Forces every actuator off, e.g. on failsafe or shutdown.
+    fn all_off(&mut self) -> Result<(), Self::Error>;
+}
+
+#[derive(Debug)]
+pub enum BoardError<F, L, H> {
+    Fan(F),
+    Led(L),
+    Heater(H),
+}
+
+/// Generic board over any HAL: PWM fan and LED channels plus a
+/// time-proportioned heater relay, each wired to one zone.
+pub struct HalBoard<F, L, H> {
+    fan: F,
+    led: L,
+    heater: HeaterRelay<H>,
+    /// Lux commanded at 100% LED duty.
+    led_full_scale_lux: u32,
+    fan_zone: ActuatorZone,
+    led_zone: ActuatorZone,
+    heater_zone: ActuatorZone,
+}
+
+impl<F, L, H> HalBoard<F, L, H>
+where
+    F: SetDutyCycle,
+    L: SetDutyCycle,
+    H: OutputPin,
+{
+    /// Wired as a single-body hive: fan and heater in the brood zone, LED at
+    /// the entrance.
+    pub fn new(fan: F, led: L, heater: HeaterRelay<H>, led_full_scale_lux: u32) -> Self {
+        Self {
+            fan,
+            led,
+            heater,
+            led_full_scale_lux: led_full_scale_lux.max(1),
+            fan_zone: ActuatorZone::Brood,
+            led_zone: ActuatorZone::Entrance,
+            heater_zone: ActuatorZone::Brood,
+        }
+    }
+
+    pub fn with_zones(mut self, fan: ActuatorZone, led: ActuatorZone, heater: ActuatorZone) -> Self {
+        self.fan_zone = fan;
+        self.led_zone = led;
+        self.heater_zone = heater;
+        self
+    }
+
+    pub fn release(self) -> (F, L, HeaterRelay<H>) {
+        (self.fan, self.led, self.heater)
+    }
+
+    fn led_duty(&self, lux: u32) -> (u16, u16) {
+        let lux = lux.min(self.led_full_scale_lux) as u64;
+        let num = lux * u16::MAX as u64 / self.led_full_scale_lux as u64;
+        (num as u16, u16::MAX)
+    }
+}
+
+impl<F, L, H> ActuatorBoard for HalBoard<F, L, H>
+where
+    F: SetDutyCycle,
+    L: SetDutyCycle,
+    H: OutputPin,
+{
+    type Error = BoardError<F::Error, L::Error, H::Error>;
+
+    fn apply(
+        &mut self,
+        frame: &ActuatorCommandFrame,
+        sensors: &SensorSnapshot,
+        tick: TickCounter,
+    ) -> Result<(), Self::Error> {
+        self.fan
+            .set_duty_cycle_percent(frame.fan_duty_pct(self.fan_zone).min(100))
+            .map_err(BoardError::Fan)?;
+        let (num, denom) = self.led_duty(frame.led_lux(self.led_zone));
+        self.led
+            .set_duty_cycle_fraction(num, denom)
+            .map_err(BoardError::Led)?;
+        self.heater
+            .update(frame.heater_celsius(self.heater_zone), sensors.brood_temp_c, tick)
+            .map_err(BoardError::Heater)
+    }
+
+    fn all_off(&mut self) -> Result<(), Self::Error> {
+        self.fan.set_duty_cycle_fully_off().map_err(BoardError::Fan)?;
+        self.led.set_duty_cycle_fully_off().map_err(BoardError::Led)?;
+        self.heater.off().map_err(BoardError::Heater)
+    }
+}
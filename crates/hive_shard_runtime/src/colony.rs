@@ -0,0 +1,183 @@
+use heapless::Deque;
+use serde::{Deserialize, Serialize};
+
+use crate::actuator::{ActuatorCommand, ActuatorCommandFrame};
+use crate::band::BandThresholds;
+use crate::sensor::SensorSnapshot;
+use crate::slew::MinMaxWindow;
+use crate::timebase::{TickCounter, MS_PER_DAY, MS_PER_HOUR};
+
+/// Phase changes buffered between calls to `pop_change`.
+pub const PHASE_CHANGE_QUEUE: usize = 4;
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum ColonyPhase {
+    BroodRearing,
+    /// Brood nest no longer regulated, but the colony is not clustering.
+    Broodless,
+    WinterCluster,
+}
+
+impl ColonyPhase {
+    /// Whether brood-nest thresholds and normal actuation apply.
+    pub fn is_brood_rearing(&self) -> bool {
+        matches!(self, ColonyPhase::BroodRearing)
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ColonyPhaseConfig {
+    /// Lowest daily brood temperature of a colony that is rearing brood.
+    pub brood_min_temp_c: i16,
+    /// Widest daily brood temperature swing of a colony rearing brood.
+    pub brood_max_daily_spread_c: i16,
+    /// Core-to-mantle gradient that indicates a formed cluster; requires a
+    /// multi-probe hive.
+    pub cluster_min_gradient_c: i16,
+    /// Consecutive hourly classifications needed before switching phase.
+    pub confirm_hours: u16,
+    pub broodless_bands: BandThresholds,
+    pub cluster_bands: BandThresholds,
+    /// Outside brood rearing the heater only runs below this core temperature.
+    pub emergency_heater_below_c: i16,
+    pub emergency_heater_max_celsius: i16,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct PhaseChange {
+    pub tick: u64,
+    pub from: ColonyPhase,
+    pub to: ColonyPhase,
+}
+
+/// Classifies the colony's phase once an hour.
+///
+/// A colony rearing brood holds the nest within a degree or two all day. Once
+/// it stops, the probe temperature drifts; a cluster additionally shows a
+/// steep gradient across probes while living off stores.
+///
+/// The gradient is only produced by probe fusion (`step_multi_probe`). A
+/// hive stepped with a single probe reports none, so it is never classified
+/// `WinterCluster` and falls back to `Broodless` once brood rearing stops.
+#[derive(Clone, Debug)]
+pub struct ColonyPhaseDetector {
+    hour_ticks: u64,
+    hour_epoch: Option<u64>,
+    daily_temp: MinMaxWindow,
+    hourly_gradient: MinMaxWindow,
+    phase: ColonyPhase,
+    candidate: ColonyPhase,
+    candidate_hours: u16,
+    changes: Deque<PhaseChange, PHASE_CHANGE_QUEUE>,
+}
+
+impl ColonyPhaseDetector {
+    pub fn new(clock: &TickCounter) -> Self {
+        let hour_ticks = clock.ticks_for_ms(MS_PER_HOUR);
+        Self {
+            hour_ticks,
+            hour_epoch: None,
+            daily_temp: MinMaxWindow::new(clock.ticks_for_ms(MS_PER_DAY)),
+            hourly_gradient: MinMaxWindow::new(hour_ticks),
+            phase: ColonyPhase::BroodRearing,
+            candidate: ColonyPhase::BroodRearing,
+            candidate_hours: 0,
+            changes: Deque::new(),
+        }
+    }
+
+    pub fn observe(
+        &mut self,
+        tick: TickCounter,
+        sensors: &SensorSnapshot,
+        net_weight_kg_x10_per_day: Option<i32>,
+        config: &ColonyPhaseConfig,
+    ) {
+        let now = tick.ticks();
+        let epoch = now / self.hour_ticks;
+        if self.hour_epoch.is_some_and(|e| e != epoch) {
+            self.classify(now, net_weight_kg_x10_per_day, config);
+        }
+        self.hour_epoch = Some(epoch);
+        self.daily_temp.record(now, sensors.brood_temp_c);
+        self.hourly_gradient.record(now, sensors.brood_gradient_c);
+    }
+
+    pub fn phase(&self) -> ColonyPhase {
+        self.phase
+    }
+
+    /// Oldest unread phase change, if any.
+    pub fn pop_change(&mut self) -> Option<PhaseChange> {
+        self.changes.pop_front()
+    }
+
+    /// Outside brood rearing the LED stays off and the heater only runs, at
+    /// reduced setpoint, when the core falls below the emergency threshold.
+    /// A winter cluster is not fed liquid syrup.
+    pub fn restrict(
+        &self,
+        frame: &mut ActuatorCommandFrame,
+        sensors: &SensorSnapshot,
+        config: &ColonyPhaseConfig,
+    ) {
+        if self.phase.is_brood_rearing() {
+            return;
+        }
+        let emergency = sensors.brood_temp_c < config.emergency_heater_below_c;
+        for channel in frame.channels.iter_mut() {
+            match &mut channel.command {
+                ActuatorCommand::Led { lux } => *lux = 0,
+                ActuatorCommand::Heater { celsius } if emergency => {
+                    *celsius = (*celsius).min(config.emergency_heater_max_celsius);
+                }
+                ActuatorCommand::Heater { celsius } => *celsius = 0,
+                ActuatorCommand::Feeder { syrup_ml } if self.phase == ColonyPhase::WinterCluster => {
+                    *syrup_ml = 0;
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn classify(
+        &mut self,
+        now: u64,
+        net_weight_kg_x10_per_day: Option<i32>,
+        config: &ColonyPhaseConfig,
+    ) {
+        let Some((min_temp, max_temp)) = self.daily_temp.range(now) else {
+            return;
+        };
+        let gradient = self.hourly_gradient.range(now).map_or(0, |(_, hi)| hi);
+        let regulated = min_temp >= config.brood_min_temp_c
+            && max_temp.saturating_sub(min_temp) <= config.brood_max_daily_spread_c;
+        let living_off_stores = net_weight_kg_x10_per_day.is_none_or(|net| net <= 0);
+
+        let observed = if regulated {
+            ColonyPhase::BroodRearing
+        } else if gradient >= config.cluster_min_gradient_c && living_off_stores {
+            ColonyPhase::WinterCluster
+        } else {
+            ColonyPhase::Broodless
+        };
+
+        if observed != self.candidate {
+            self.candidate = observed;
+            self.candidate_hours = 0;
+        }
+        self.candidate_hours = self.candidate_hours.saturating_add(1);
+        if observed != self.phase && self.candidate_hours >= config.confirm_hours {
+            let change = PhaseChange {
+                tick: now,
+                from: self.phase,
+                to: observed,
+            };
+            self.phase = observed;
+            if self.changes.is_full() {
+                self.changes.pop_front();
+            }
+            let _ = self.changes.push_back(change);
+        }
+    }
+}
@@ -1,13 +1,41 @@
 use serde::{Deserialize, Serialize};
 
 use crate::band::{BandThresholds, BioloadThresholds};
+use crate::colony::ColonyPhaseConfig;
+use crate::duty::DutyCyclePolicy;
+use crate::failsafe::FailsafePolicy;
+use crate::fusion::ProbeFusion;
+use crate::homing::HomingConfig;
 use crate::limits::{ActuationCaps, QuotaProfile, ShardLimits};
+use crate::season::SeasonalThresholds;
+use crate::persist::PersistPolicy;
+use crate::power::PowerPolicy;
+use crate::sensor_health::SensorPlausibility;
+use crate::watchdog::WatchdogPolicy;
+use crate::weight::WeightTrendConfig;
+use crate::yellow::YellowBudget;
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct ShardConfig {
+    pub tick_period_ms: u32,
     pub limits: ShardLimits,
+    pub probe_fusion: ProbeFusion,
+    pub sensor_plausibility: SensorPlausibility,
     pub bands: BandThresholds,
     pub bioload_thresholds: BioloadThresholds,
+    /// Replaces `bands` and `bioload_thresholds` by date once the clock is
+    /// anchored to UTC.
+    pub seasonal: Option<SeasonalThresholds>,
+    pub colony_phase: ColonyPhaseConfig,
     pub quota_profile: QuotaProfile,
     pub actuation_caps: ActuationCaps,
+    pub duty_cycle: DutyCyclePolicy,
+    /// Battery-powered shards only; mains-powered shards leave it unset.
+    pub power: Option<PowerPolicy>,
+    pub yellow_budget: YellowBudget,
+    pub failsafe: FailsafePolicy,
+    pub watchdog: WatchdogPolicy,
+    pub weight_trend: WeightTrendConfig,
+    pub homing: HomingConfig,
+    pub persist: PersistPolicy,
 }
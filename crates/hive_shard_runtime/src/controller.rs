@@ -1,9 +1,40 @@
+use serde::{Deserialize, Serialize};
+
 use crate::actuator::ActuatorCommandFrame;
+use crate::quota::QuotaUsage;
 use crate::sensor::SensorSnapshot;
 
+/// Resources a controller spent on its most recent `step_neuromorphic`.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct StepUsage {
+    pub spikes: u32,
+    pub synaptic_ops: u32,
+    pub energy_uj: u32,
+    /// Zero when the controller cannot time itself.
+    pub wall_time_us: u32,
+}
+
+impl StepUsage {
+    /// One inference, with energy rounded up to whole millijoules.
+    pub fn quota_usage(&self) -> QuotaUsage {
+        QuotaUsage {
+            inferences: 1,
+            spikes: self.spikes,
+            energy_mj: self.energy_uj.div_ceil(1000),
+        }
+    }
+}
+
 pub trait NeuromorphicController {
     fn step_neuromorphic(
         &mut self,
         sensors: &SensorSnapshot,
     ) -> ActuatorCommandFrame;
+
+    /// Usage report for the step that just ran. Controllers that cannot
+    /// measure themselves report zeros, which only the ops and inference-rate
+    /// quotas then constrain.
+    fn step_usage(&self) -> StepUsage {
+        StepUsage::default()
+    }
 }
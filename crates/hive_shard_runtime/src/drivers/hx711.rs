@@ -0,0 +1,125 @@
+use embedded_hal::delay::DelayNs;
+use embedded_hal::digital::{InputPin, OutputPin};
+
+use super::WeightSensor;
+
+/// Ready polls before a read is abandoned; at 10 SPS the HX711 is ready
+/// within 100 ms.
+const READY_POLLS: u32 = 120;
+const READY_POLL_US: u32 = 1_000;
+const CLOCK_HALF_PERIOD_US: u32 = 1;
+
+/// Channel and gain for the next conversion, encoded as extra clock pulses.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Hx711Gain {
+    ChannelA128 = 1,
+    ChannelB32 = 2,
+    ChannelA64 = 3,
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct LoadCellCalibration {
+    /// Raw reading with the empty hive stand.
+    pub tare_offset: i32,
+    /// Counts per kilogram; negative when the cell is wired in reverse.
+    pub counts_per_kg: i32,
+}
+
+#[derive(Debug)]
+pub enum Hx711Error<EI, EO> {
+    Data(EI),
+    Clock(EO),
+    NotReady,
+    /// `counts_per_kg` is zero.
+    Uncalibrated,
+}
+
+/// Bit-banged driver for HX711-style 24-bit load-cell ADCs.
+pub struct Hx711<DOUT, SCK, D> {
+    dout: DOUT,
+    sck: SCK,
+    delay: D,
+    gain: Hx711Gain,
+    calibration: LoadCellCalibration,
+}
+
+impl<DOUT, SCK, D> Hx711<DOUT, SCK, D>
+where
+    DOUT: InputPin,
+    SCK: OutputPin,
+    D: DelayNs,
+{
+    pub fn new(
+        dout: DOUT,
+        sck: SCK,
+        delay: D,
+        gain: Hx711Gain,
+        calibration: LoadCellCalibration,
+    ) -> Self {
+        Self {
+            dout,
+            sck,
+            delay,
+            gain,
+            calibration,
+        }
+    }
+
+    pub fn set_calibration(&mut self, calibration: LoadCellCalibration) {
+        self.calibration = calibration;
+    }
+
+    /// Signed 24-bit conversion result.
+    pub fn read_raw(&mut self) -> Result<i32, Hx711Error<DOUT::Error, SCK::Error>> {
+        let mut polls = 0;
+        while self.dout.is_high().map_err(Hx711Error::Data)? {
+            polls += 1;
+            if polls >= READY_POLLS {
+                return Err(Hx711Error::NotReady);
+            }
+            self.delay.delay_us(READY_POLL_US);
+        }
+
+        let mut raw: u32 = 0;
+        for _ in 0..24 {
+            self.pulse()?;
+            raw = (raw << 1) | self.dout.is_high().map_err(Hx711Error::Data)? as u32;
+        }
+        for _ in 0..self.gain as u8 {
+            self.pulse()?;
+        }
+        // Sign-extend from 24 bits.
+        Ok(((raw << 8) as i32) >> 8)
+    }
+
+    pub fn release(self) -> (DOUT, SCK, D) {
+        (self.dout, self.sck, self.delay)
+    }
+
+    fn pulse(&mut self) -> Result<(), Hx711Error<DOUT::Error, SCK::Error>> {
+        self.sck.set_high().map_err(Hx711Error::Clock)?;
+        self.delay.delay_us(CLOCK_HALF_PERIOD_US);
+        self.sck.set_low().map_err(Hx711Error::Clock)?;
+        self.delay.delay_us(CLOCK_HALF_PERIOD_US);
+        Ok(())
+    }
+}
+
+impl<DOUT, SCK, D> WeightSensor for Hx711<DOUT, SCK, D>
+where
+    DOUT: InputPin,
+    SCK: OutputPin,
+    D: DelayNs,
+{
+    type Error = Hx711Error<DOUT::Error, SCK::Error>;
+
+    fn read_kg_x10(&mut self) -> Result<i32, Self::Error> {
+        if self.calibration.counts_per_kg == 0 {
+            return Err(Hx711Error::Uncalibrated);
+        }
+        let raw = self.read_raw()? as i64;
+        let cal = &self.calibration;
+        let kg_x10 = (raw - cal.tare_offset as i64) * 10 / cal.counts_per_kg as i64;
+        Ok(kg_x10.clamp(i32::MIN as i64, i32::MAX as i64) as i32)
+    }
+}
@@ -0,0 +1,178 @@
+//! Transaction-script doubles for exercising drivers on the host.
+
+use core::convert::Infallible;
+
+use embedded_hal::delay::DelayNs;
+use embedded_hal::digital::{self, InputPin, OutputPin};
+use embedded_hal::i2c::{self, ErrorKind, I2c, Operation};
+use heapless::{Deque, Vec};
+
+/// Longest payload a scripted transaction can carry.
+pub const SCRIPT_MAX_BYTES: usize = 8;
+pub const SCRIPT_MAX_STEPS: usize = 32;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum I2cStep {
+    /// Expect a write of exactly these bytes.
+    Write(u8, Vec<u8, SCRIPT_MAX_BYTES>),
+    /// Expect a read and answer with these bytes.
+    Read(u8, Vec<u8, SCRIPT_MAX_BYTES>),
+    /// Expect any operation on this address and fail it.
+    Fail(u8),
+}
+
+impl I2cStep {
+    pub fn write(address: u8, bytes: &[u8]) -> Self {
+        I2cStep::Write(address, Vec::from_slice(bytes).unwrap_or_default())
+    }
+
+    pub fn read(address: u8, bytes: &[u8]) -> Self {
+        I2cStep::Read(address, Vec::from_slice(bytes).unwrap_or_default())
+    }
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum ScriptError {
+    Unexpected,
+    Injected,
+}
+
+impl i2c::Error for ScriptError {
+    fn kind(&self) -> ErrorKind {
+        ErrorKind::Other
+    }
+}
+
+/// I2C bus that replays an expected sequence of operations, in the spirit
+/// of `embedded-hal-mock`.
+#[derive(Debug, Default)]
+pub struct ScriptedI2c {
+    steps: Deque<I2cStep, SCRIPT_MAX_STEPS>,
+    mismatches: u32,
+}
+
+impl ScriptedI2c {
+    pub fn new(steps: &[I2cStep]) -> Self {
+        let mut script = Self::default();
+        for step in steps {
+            let _ = script.steps.push_back(step.clone());
+        }
+        script
+    }
+
+    /// True once every step ran and nothing unexpected happened.
+    pub fn is_done(&self) -> bool {
+        self.steps.is_empty() && self.mismatches == 0
+    }
+
+    pub fn mismatches(&self) -> u32 {
+        self.mismatches
+    }
+
+    fn expect(&mut self, address: u8, op: &mut Operation<'_>) -> Result<(), ScriptError> {
+        let result = match (self.steps.pop_front(), op) {
+            (Some(I2cStep::Write(a, bytes)), Operation::Write(data))
+                if a == address && bytes.as_slice() == *data =>
+            {
+                Ok(())
+            }
+            (Some(I2cStep::Read(a, bytes)), Operation::Read(buf))
+                if a == address && bytes.len() == buf.len() =>
+            {
+                buf.copy_from_slice(&bytes);
+                Ok(())
+            }
+            (Some(I2cStep::Fail(a)), _) if a == address => return Err(ScriptError::Injected),
+            _ => Err(ScriptError::Unexpected),
+        };
+        if result.is_err() {
+            self.mismatches += 1;
+        }
+        result
+    }
+}
+
+impl i2c::ErrorType for ScriptedI2c {
+    type Error = ScriptError;
+}
+
+impl I2c for ScriptedI2c {
+    fn transaction(
+        &mut self,
+        address: u8,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        for op in operations.iter_mut() {
+            self.expect(address, op)?;
+        }
+        Ok(())
+    }
+}
+
+/// Input pin returning a scripted sequence of levels, then low forever.
+#[derive(Debug, Default)]
+pub struct ScriptedInputPin {
+    levels: Deque<bool, 64>,
+}
+
+impl ScriptedInputPin {
+    pub fn new(levels: &[bool]) -> Self {
+        let mut pin = Self::default();
+        for level in levels {
+            let _ = pin.levels.push_back(*level);
+        }
+        pin
+    }
+}
+
+impl digital::ErrorType for ScriptedInputPin {
+    type Error = Infallible;
+}
+
+impl InputPin for ScriptedInputPin {
+    fn is_high(&mut self) -> Result<bool, Self::Error> {
+        Ok(self.levels.pop_front().unwrap_or(false))
+    }
+
+    fn is_low(&mut self) -> Result<bool, Self::Error> {
+        self.is_high().map(|high| !high)
+    }
+}
+
+/// Output pin that counts rising edges, e.g. clock pulses.
+#[derive(Debug, Default)]
+pub struct CountingOutputPin {
+    high: bool,
+    pub rising_edges: u32,
+}
+
+impl digital::ErrorType for CountingOutputPin {
+    type Error = Infallible;
+}
+
+impl OutputPin for CountingOutputPin {
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        self.high = false;
+        Ok(())
+    }
+
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        if !self.high {
+            self.rising_edges += 1;
+        }
+        self.high = true;
+        Ok(())
+    }
+}
+
+/// Delay that returns immediately while counting requested time.
+#[derive(Debug, Default)]
+pub struct NoopDelay {
+    pub total_ns: u64,
+}
+
+impl DelayNs for NoopDelay {
+    fn delay_ns(&mut self, ns: u32) {
+        self.total_ns += ns as u64;
+    }
+}
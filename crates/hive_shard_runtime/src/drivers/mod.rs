@@ -0,0 +1,116 @@
+//! embedded-hal 1.0 drivers for hive sensors and the `SensorAcquisition`
+//! rig that turns their readings into a `SensorSnapshot`.
+
+pub mod sensirion;
+pub mod sht3x;
+pub mod sht4x;
+pub mod hx711;
+pub mod mock;
+
+use crate::sensor::{SensorFields, SensorSnapshot};
+use crate::timebase::TickCounter;
+
+/// Temperature and relative humidity in hundredths.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct TempHumidity {
+    pub temp_c_x100: i32,
+    pub humidity_pct_x100: i32,
+}
+
+impl TempHumidity {
+    pub fn temp_c(&self) -> i16 {
+        round_hundredths(self.temp_c_x100).clamp(i16::MIN as i32, i16::MAX as i32) as i16
+    }
+
+    pub fn humidity_pct(&self) -> u8 {
+        round_hundredths(self.humidity_pct_x100).clamp(0, 100) as u8
+    }
+}
+
+fn round_hundredths(x100: i32) -> i32 {
+    if x100 >= 0 {
+        (x100 + 50) / 100
+    } else {
+        (x100 - 50) / 100
+    }
+}
+
+pub trait TempHumiditySensor {
+    type Error;
+
+    fn read(&mut self) -> Result<TempHumidity, Self::Error>;
+}
+
+pub trait WeightSensor {
+    type Error;
+
+    fn read_kg_x10(&mut self) -> Result<i32, Self::Error>;
+}
+
+/// Produces one `SensorSnapshot` per control period.
+///
+/// Failed reads are reported through `SensorSnapshot::missing` rather than as
+/// errors, so the runtime's plausibility layer decides what a gap means.
+pub trait SensorAcquisition {
+    fn acquire(&mut self, tick: TickCounter) -> SensorSnapshot;
+}
+
+/// Acquisition rig for a brood temperature/humidity sensor and a hive scale.
+///
+/// Readings without a driver (acoustic surplus, mortality, forager returns,
+/// varroa counts) are carried over from `set_external`.
+pub struct HiveSensorRig<T, W> {
+    brood: T,
+    scale: W,
+    external: SensorSnapshot,
+}
+
+impl<T, W> HiveSensorRig<T, W>
+where
+    T: TempHumiditySensor,
+    W: WeightSensor,
+{
+    pub fn new(brood: T, scale: W, external: SensorSnapshot) -> Self {
+        Self {
+            brood,
+            scale,
+            external,
+        }
+    }
+
+    /// Updates the readings that come from outside this rig.
+    pub fn set_external(&mut self, external: SensorSnapshot) {
+        self.external = external;
+    }
+
+    pub fn release(self) -> (T, W) {
+        (self.brood, self.scale)
+    }
+}
+
+impl<T, W> SensorAcquisition for HiveSensorRig<T, W>
+where
+    T: TempHumiditySensor,
+    W: WeightSensor,
+{
+    fn acquire(&mut self, _tick: TickCounter) -> SensorSnapshot {
+        let mut snapshot = self.external.clone();
+        let owned = SensorFields::BROOD_TEMP | SensorFields::BROOD_HUMIDITY | SensorFields::HIVE_WEIGHT;
+        snapshot.missing.remove(owned);
+
+        match self.brood.read() {
+            Ok(reading) => {
+                snapshot.brood_temp_c = reading.temp_c();
+                snapshot.brood_humidity_pct = reading.humidity_pct();
+            }
+            Err(_) => snapshot
+                .missing
+                .insert(SensorFields::BROOD_TEMP | SensorFields::BROOD_HUMIDITY),
+        }
+        match self.scale.read_kg_x10() {
+            Ok(weight) => snapshot.hive_weight_kg_x10 = weight,
+            Err(_) => snapshot.missing.insert(SensorFields::HIVE_WEIGHT),
+        }
+        snapshot
+    }
+}
@@ -0,0 +1,38 @@
+//! Framing shared by Sensirion SHT3x and SHT4x sensors.
+
+/// CRC-8, polynomial 0x31, init 0xFF.
+pub fn crc8(data: &[u8]) -> u8 {
+    let mut crc = 0xFFu8;
+    for byte in data {
+        crc ^= byte;
+        for _ in 0..8 {
+            crc = if crc & 0x80 != 0 {
+                (crc << 1) ^ 0x31
+            } else {
+                crc << 1
+            };
+        }
+    }
+    crc
+}
+
+/// Splits a 6-byte `T T crc RH RH crc` frame into raw words, checking CRCs.
+pub fn decode_frame(frame: &[u8; 6]) -> Option<(u16, u16)> {
+    if crc8(&frame[0..2]) != frame[2] || crc8(&frame[3..5]) != frame[5] {
+        return None;
+    }
+    Some((
+        u16::from_be_bytes([frame[0], frame[1]]),
+        u16::from_be_bytes([frame[3], frame[4]]),
+    ))
+}
+
+/// `-45 + 175 * raw / 65535` °C, in hundredths.
+pub fn temp_c_x100(raw: u16) -> i32 {
+    -4500 + (17500 * raw as i32) / 65535
+}
+
+/// `offset + scale * raw / 65535` %RH, in hundredths.
+pub fn humidity_pct_x100(raw: u16, offset: i32, scale: i32) -> i32 {
+    offset * 100 + (scale * 100 * raw as i32) / 65535
+}
@@ -0,0 +1,57 @@
+use embedded_hal::delay::DelayNs;
+use embedded_hal::i2c::I2c;
+
+use super::sensirion;
+use super::{TempHumidity, TempHumiditySensor};
+
+pub const SHT3X_DEFAULT_ADDRESS: u8 = 0x44;
+
+/// Single shot, high repeatability, no clock stretching.
+const CMD_MEASURE_HIGH: [u8; 2] = [0x24, 0x00];
+const MEASURE_DELAY_MS: u32 = 16;
+
+#[derive(Debug)]
+pub enum Sht3xError<E> {
+    I2c(E),
+    Crc,
+}
+
+pub struct Sht3x<I, D> {
+    i2c: I,
+    delay: D,
+    address: u8,
+}
+
+impl<I: I2c, D: DelayNs> Sht3x<I, D> {
+    pub fn new(i2c: I, delay: D, address: u8) -> Self {
+        Self {
+            i2c,
+            delay,
+            address,
+        }
+    }
+
+    pub fn release(self) -> (I, D) {
+        (self.i2c, self.delay)
+    }
+}
+
+impl<I: I2c, D: DelayNs> TempHumiditySensor for Sht3x<I, D> {
+    type Error = Sht3xError<I::Error>;
+
+    fn read(&mut self) -> Result<TempHumidity, Self::Error> {
+        self.i2c
+            .write(self.address, &CMD_MEASURE_HIGH)
+            .map_err(Sht3xError::I2c)?;
+        self.delay.delay_ms(MEASURE_DELAY_MS);
+        let mut frame = [0u8; 6];
+        self.i2c
+            .read(self.address, &mut frame)
+            .map_err(Sht3xError::I2c)?;
+        let (t, rh) = sensirion::decode_frame(&frame).ok_or(Sht3xError::Crc)?;
+        Ok(TempHumidity {
+            temp_c_x100: sensirion::temp_c_x100(t),
+            humidity_pct_x100: sensirion::humidity_pct_x100(rh, 0, 100),
+        })
+    }
+}
@@ -0,0 +1,58 @@
+use embedded_hal::delay::DelayNs;
+use embedded_hal::i2c::I2c;
+
+use super::sensirion;
+use super::{TempHumidity, TempHumiditySensor};
+
+pub const SHT4X_DEFAULT_ADDRESS: u8 = 0x44;
+
+/// Measure T & RH with high precision.
+const CMD_MEASURE_HIGH: u8 = 0xFD;
+const MEASURE_DELAY_MS: u32 = 10;
+
+#[derive(Debug)]
+pub enum Sht4xError<E> {
+    I2c(E),
+    Crc,
+}
+
+pub struct Sht4x<I, D> {
+    i2c: I,
+    delay: D,
+    address: u8,
+}
+
+impl<I: I2c, D: DelayNs> Sht4x<I, D> {
+    pub fn new(i2c: I, delay: D, address: u8) -> Self {
+        Self {
+            i2c,
+            delay,
+            address,
+        }
+    }
+
+    pub fn release(self) -> (I, D) {
+        (self.i2c, self.delay)
+    }
+}
+
+impl<I: I2c, D: DelayNs> TempHumiditySensor for Sht4x<I, D> {
+    type Error = Sht4xError<I::Error>;
+
+    fn read(&mut self) -> Result<TempHumidity, Self::Error> {
+        self.i2c
+            .write(self.address, &[CMD_MEASURE_HIGH])
+            .map_err(Sht4xError::I2c)?;
+        self.delay.delay_ms(MEASURE_DELAY_MS);
+        let mut frame = [0u8; 6];
+        self.i2c
+            .read(self.address, &mut frame)
+            .map_err(Sht4xError::I2c)?;
+        let (t, rh) = sensirion::decode_frame(&frame).ok_or(Sht4xError::Crc)?;
+        Ok(TempHumidity {
+            temp_c_x100: sensirion::temp_c_x100(t),
+            // The SHT4x RH transfer function can leave 0..100 %; clamp it.
+            humidity_pct_x100: sensirion::humidity_pct_x100(rh, -6, 125).clamp(0, 10_000),
+        })
+    }
+}
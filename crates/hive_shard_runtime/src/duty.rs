@@ -0,0 +1,137 @@
+use heapless::Vec;
+use serde::{Deserialize, Serialize};
+
+use crate::actuator::{ActuatorCommandFrame, ActuatorKind, ActuatorZone};
+use crate::limits::ShardLimits;
+use crate::quota::SlidingWindow;
+use crate::timebase::{TickCounter, MS_PER_MINUTE, MS_PER_SECOND};
+
+/// Powered actuator kinds subject to the duty-cycle budget.
+pub const DUTY_CYCLED: [ActuatorKind; 4] = [
+    ActuatorKind::Heater,
+    ActuatorKind::Fan,
+    ActuatorKind::Led,
+    ActuatorKind::Feeder,
+];
+
+/// Upper bound on entries in a `DutyCycleTracker::report`.
+pub const DUTY_REPORT_LEN: usize = ZONES * DUTY_CYCLED.len();
+
+const ZONES: usize = ActuatorZone::ALL.len();
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct DutyCyclePolicy {
+    /// Rolling window `ShardLimits::max_actuator_duty_cycle_pct` applies over.
+    pub window_min: u32,
+}
+
+/// On-time used and left for one actuator over the current window.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct DutyBudget {
+    pub zone: ActuatorZone,
+    pub actuator: ActuatorKind,
+    pub on_secs: u32,
+    pub remaining_secs: u32,
+}
+
+/// Per-zone, per-actuator on-time over a rolling window.
+///
+/// Each period an energized actuator is charged one tick; once its share
+/// of the window reaches `max_actuator_duty_cycle_pct` it is switched off
+/// until older on-time ages out.
+#[derive(Clone, Debug)]
+pub struct DutyCycleTracker {
+    window_ticks: u64,
+    on_ticks: [[SlidingWindow; DUTY_CYCLED.len()]; ZONES],
+}
+
+impl DutyCycleTracker {
+    pub fn new(policy: &DutyCyclePolicy, clock: &TickCounter) -> Self {
+        let window_ticks = clock
+            .ticks_for_ms(policy.window_min as u64 * MS_PER_MINUTE)
+            .max(1);
+        Self {
+            window_ticks,
+            on_ticks: core::array::from_fn(|_| {
+                core::array::from_fn(|_| SlidingWindow::new(window_ticks))
+            }),
+        }
+    }
+
+    /// Switches off every actuator whose budget is spent and charges the
+    /// rest for this period. A limit of 100 % or more only tracks on-time.
+    pub fn enforce(
+        &mut self,
+        frame: &mut ActuatorCommandFrame,
+        tick: TickCounter,
+        limits: &ShardLimits,
+    ) {
+        let now = tick.ticks();
+        let budget = self.budget_ticks(limits);
+        let unlimited = limits.max_actuator_duty_cycle_pct >= 100;
+        for channel in frame.channels.iter_mut() {
+            if !channel.command.is_energized() {
+                continue;
+            }
+            let Some(slot) = slot(channel.command.kind()) else {
+                continue;
+            };
+            let window = &mut self.on_ticks[channel.zone.index()][slot];
+            if !unlimited && window.total(now) as u64 >= budget {
+                channel.command.switch_off();
+            } else {
+                window.add(now, 1);
+            }
+        }
+    }
+
+    /// Budget of `actuator` in `zone`; `None` for kinds that are not
+    /// duty-cycled.
+    pub fn budget(
+        &self,
+        zone: ActuatorZone,
+        actuator: ActuatorKind,
+        tick: TickCounter,
+        limits: &ShardLimits,
+    ) -> Option<DutyBudget> {
+        let slot = slot(actuator)?;
+        let on = self.on_ticks[zone.index()][slot].total(tick.ticks()) as u64;
+        let remaining = self.budget_ticks(limits).saturating_sub(on);
+        let secs =
+            |ticks: u64| (tick.ticks_to_ms(ticks) / MS_PER_SECOND).min(u32::MAX as u64) as u32;
+        Some(DutyBudget {
+            zone,
+            actuator,
+            on_secs: secs(on),
+            remaining_secs: secs(remaining),
+        })
+    }
+
+    /// Budgets of every actuator that has been on during the window.
+    pub fn report(
+        &self,
+        tick: TickCounter,
+        limits: &ShardLimits,
+    ) -> Vec<DutyBudget, DUTY_REPORT_LEN> {
+        let mut out = Vec::new();
+        for zone in ActuatorZone::ALL {
+            for (slot, actuator) in DUTY_CYCLED.into_iter().enumerate() {
+                if self.on_ticks[zone.index()][slot].total(tick.ticks()) == 0 {
+                    continue;
+                }
+                if let Some(budget) = self.budget(zone, actuator, tick, limits) {
+                    let _ = out.push(budget);
+                }
+            }
+        }
+        out
+    }
+
+    fn budget_ticks(&self, limits: &ShardLimits) -> u64 {
+        self.window_ticks * limits.max_actuator_duty_cycle_pct.min(100) as u64 / 100
+    }
+}
+
+fn slot(actuator: ActuatorKind) -> Option<usize> {
+    DUTY_CYCLED.iter().position(|k| *k == actuator)
+}
@@ -9,10 +9,6 @@ impl NeuromorphicController for DummySnnController {
         &mut self,
         _sensors: &SensorSnapshot,
     ) -> ActuatorCommandFrame {
-        ActuatorCommandFrame {
-            heater_celsius: 0,
-            fan_duty_pct: 0,
-            led_lux: 0,
-        }
+        ActuatorCommandFrame::observation_only()
     }
 }
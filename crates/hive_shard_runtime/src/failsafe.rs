@@ -1,11 +1,216 @@
-#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+use serde::{Deserialize, Serialize};
+
+use crate::band::BandState;
+use crate::limits::ActuationCaps;
+use crate::quota::QuotaViolation;
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub enum FailsafeMode {
     Normal,
+    Degraded,
     ObservationOnly,
+    Recovering,
 }
 
 impl FailsafeMode {
     pub fn is_observation_only(&self) -> bool {
         matches!(self, FailsafeMode::ObservationOnly)
     }
+
+    /// Whether actuation runs under the reduced probation caps.
+    pub fn is_probationary(&self) -> bool {
+        matches!(self, FailsafeMode::Degraded | FailsafeMode::Recovering)
+    }
+}
+
+/// Why the runtime changed failsafe mode.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum FailsafeReason {
+    RedBand,
+    CriticalBioload,
+    YellowBudgetExhausted,
+    SensorFault,
+    DeadlineOverrun,
+    /// Foragers failing to return alongside rising mortality.
+    ExposureIncident,
+    /// The controller filled its frame with repeated channels, leaving no
+    /// room for the entrance reducer floor.
+    FrameOverflow,
+    Quota(QuotaViolation),
+    YellowBand,
+    SustainedGreen,
+    ProbationRelapse,
+    ProbationComplete,
+}
+
+impl FailsafeReason {
+    pub fn reason_code(&self) -> u8 {
+        match self {
+            FailsafeReason::RedBand => 0x01,
+            FailsafeReason::CriticalBioload => 0x02,
+            FailsafeReason::YellowBudgetExhausted => 0x03,
+            FailsafeReason::SensorFault => 0x04,
+            FailsafeReason::DeadlineOverrun => 0x05,
+            FailsafeReason::ExposureIncident => 0x06,
+            FailsafeReason::FrameOverflow => 0x07,
+            FailsafeReason::Quota(violation) => violation.reason_code(),
+            FailsafeReason::YellowBand => 0x20,
+            FailsafeReason::SustainedGreen => 0x21,
+            FailsafeReason::ProbationRelapse => 0x22,
+            FailsafeReason::ProbationComplete => 0x23,
+        }
+    }
+
+    pub fn from_reason_code(code: u8) -> Option<Self> {
+        let reason = match code {
+            0x01 => FailsafeReason::RedBand,
+            0x02 => FailsafeReason::CriticalBioload,
+            0x03 => FailsafeReason::YellowBudgetExhausted,
+            0x04 => FailsafeReason::SensorFault,
+            0x05 => FailsafeReason::DeadlineOverrun,
+            0x06 => FailsafeReason::ExposureIncident,
+            0x07 => FailsafeReason::FrameOverflow,
+            0x20 => FailsafeReason::YellowBand,
+            0x21 => FailsafeReason::SustainedGreen,
+            0x22 => FailsafeReason::ProbationRelapse,
+            0x23 => FailsafeReason::ProbationComplete,
+            _ => return QuotaViolation::from_reason_code(code).map(FailsafeReason::Quota),
+        };
+        Some(reason)
+    }
+}
+
+/// Dwell times and re-arm requirements for leaving observation-only.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct FailsafePolicy {
+    pub min_observation_dwell_ticks: u32,
+    pub recovery_green_samples: u16,
+    pub probation_ticks: u32,
+    pub probation_caps: ActuationCaps,
+}
+
+/// A mode transition, or with `from == to` a notification that changed no
+/// mode, such as an exhausted yellow budget under `YellowBudgetAction::Notify`.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct FailsafeEvent {
+    pub tick: u64,
+    pub from: FailsafeMode,
+    pub to: FailsafeMode,
+    pub reason: FailsafeReason,
+}
+
+/// Normal → Degraded → ObservationOnly → Recovering → Normal.
+///
+/// Hard conditions (red band, critical bioload, exhausted budgets) trip
+/// straight to `ObservationOnly` from any mode and restart the dwell timer.
+/// Re-arming requires the dwell to elapse and a run of consecutive green
+/// samples, followed by a probation phase under reduced caps; any non-green
+/// sample during probation falls back to `ObservationOnly`.
+#[derive(Clone, Debug)]
+pub struct FailsafeMachine {
+    mode: FailsafeMode,
+    reason: Option<FailsafeReason>,
+    since: u64,
+    green_streak: u16,
+}
+
+impl FailsafeMachine {
+    pub fn new() -> Self {
+        Self {
+            mode: FailsafeMode::Normal,
+            reason: None,
+            since: 0,
+            green_streak: 0,
+        }
+    }
+
+    pub fn mode(&self) -> FailsafeMode {
+        self.mode
+    }
+
+    /// Reason for the most recent transition, `None` while never tripped.
+    pub fn reason(&self) -> Option<FailsafeReason> {
+        self.reason
+    }
+
+    pub fn trip(&mut self, now: u64, reason: FailsafeReason) -> Option<FailsafeEvent> {
+        self.since = now;
+        self.green_streak = 0;
+        if self.mode.is_observation_only() {
+            return None;
+        }
+        self.transition(now, FailsafeMode::ObservationOnly, reason)
+    }
+
+    /// Resumes in `mode` without reporting a transition, e.g. after a reboot.
+    pub(crate) fn restore(&mut self, now: u64, mode: FailsafeMode, reason: Option<FailsafeReason>) {
+        self.mode = mode;
+        self.reason = reason;
+        self.since = now;
+        self.green_streak = 0;
+    }
+
+    /// Advances the machine with a band sample that raised no hard condition.
+    pub fn update(
+        &mut self,
+        now: u64,
+        band: BandState,
+        policy: &FailsafePolicy,
+    ) -> Option<FailsafeEvent> {
+        if band == BandState::Green {
+            self.green_streak = self.green_streak.saturating_add(1);
+        } else {
+            self.green_streak = 0;
+        }
+        let dwell = now.saturating_sub(self.since);
+
+        match self.mode {
+            FailsafeMode::Normal if band != BandState::Green => {
+                self.transition(now, FailsafeMode::Degraded, FailsafeReason::YellowBand)
+            }
+            FailsafeMode::Degraded if self.green_streak >= policy.recovery_green_samples => {
+                self.transition(now, FailsafeMode::Normal, FailsafeReason::SustainedGreen)
+            }
+            FailsafeMode::ObservationOnly
+                if dwell >= policy.min_observation_dwell_ticks as u64
+                    && self.green_streak >= policy.recovery_green_samples =>
+            {
+                self.transition(now, FailsafeMode::Recovering, FailsafeReason::SustainedGreen)
+            }
+            FailsafeMode::Recovering if band != BandState::Green => self.transition(
+                now,
+                FailsafeMode::ObservationOnly,
+                FailsafeReason::ProbationRelapse,
+            ),
+            FailsafeMode::Recovering if dwell >= policy.probation_ticks as u64 => {
+                self.transition(now, FailsafeMode::Normal, FailsafeReason::ProbationComplete)
+            }
+            _ => None,
+        }
+    }
+
+    fn transition(
+        &mut self,
+        now: u64,
+        to: FailsafeMode,
+        reason: FailsafeReason,
+    ) -> Option<FailsafeEvent> {
+        let from = self.mode;
+        self.mode = to;
+        self.reason = Some(reason);
+        self.since = now;
+        self.green_streak = 0;
+        Some(FailsafeEvent {
+            tick: now,
+            from,
+            to,
+            reason,
+        })
+    }
+}
+
+impl Default for FailsafeMachine {
+    fn default() -> Self {
+        Self::new()
+    }
 }
@@ -0,0 +1,53 @@
+use crate::actuator::{ActuatorCommand, ActuatorCommandFrame};
+use crate::limits::ActuationCaps;
+use crate::quota::SlidingWindow;
+use crate::timebase::{TickCounter, MS_PER_DAY};
+
+/// Rolling 24 h account of syrup pumped by the feeders, across all zones.
+#[derive(Clone, Debug)]
+pub struct FeederLedger {
+    dispensed_ml: SlidingWindow,
+}
+
+impl FeederLedger {
+    pub fn new(clock: &TickCounter) -> Self {
+        Self {
+            dispensed_ml: SlidingWindow::new(clock.ticks_for_ms(MS_PER_DAY)),
+        }
+    }
+
+    pub fn dispensed_ml_today(&self, now: u64) -> u32 {
+        self.dispensed_ml.total(now)
+    }
+
+    /// Trims feeder channels, in frame order, to what remains of the daily
+    /// volume in `caps`.
+    pub fn limit(&self, frame: &mut ActuatorCommandFrame, tick: TickCounter, caps: &ActuationCaps) {
+        let Some(feeder) = &caps.feeder else {
+            return;
+        };
+        let mut remaining = feeder
+            .max_ml_per_day
+            .saturating_sub(self.dispensed_ml_today(tick.ticks()));
+        for channel in frame.channels.iter_mut() {
+            if let ActuatorCommand::Feeder { syrup_ml } = &mut channel.command {
+                *syrup_ml = (*syrup_ml as u32).min(remaining) as u16;
+                remaining -= *syrup_ml as u32;
+            }
+        }
+    }
+
+    pub fn record(&mut self, frame: &ActuatorCommandFrame, tick: TickCounter) {
+        let total = frame
+            .channels
+            .iter()
+            .map(|c| match c.command {
+                ActuatorCommand::Feeder { syrup_ml } => syrup_ml as u32,
+                _ => 0,
+            })
+            .sum::<u32>();
+        if total > 0 {
+            self.dispensed_ml.add(tick.ticks(), total);
+        }
+    }
+}
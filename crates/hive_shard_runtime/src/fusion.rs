@@ -0,0 +1,158 @@
+use heapless::Vec;
+use serde::{Deserialize, Serialize};
+
+use crate::sensor::{SensorFields, SensorSnapshot};
+
+/// Brood probes a single hive body can carry.
+pub const MAX_BROOD_PROBES: usize = 5;
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ProbePosition {
+    /// Frame slot counted from the left wall of the brood box.
+    pub frame: u8,
+    pub height_mm: u16,
+}
+
+#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
+pub struct BroodProbe {
+    pub position: ProbePosition,
+    /// `None` when the probe could not be read this tick.
+    pub temp_c: Option<i16>,
+    /// `None` for temperature-only probes or failed reads.
+    pub humidity_pct: Option<u8>,
+}
+
+/// Snapshot from a hive with several brood probes; the brood fields of
+/// `base` are overwritten by `fuse`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct MultiProbeSnapshot {
+    pub base: SensorSnapshot,
+    pub probes: Vec<BroodProbe, MAX_BROOD_PROBES>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ProbeFusion {
+    /// Probes further than this from the median are rejected as outliers.
+    pub outlier_max_dev_c: i16,
+    pub outlier_max_dev_humidity_pct: u8,
+    /// Readings dropped from each end before averaging, when enough remain.
+    pub trim: u8,
+}
+
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct FusionReport {
+    pub temp_probes_used: u8,
+    pub humidity_probes_used: u8,
+    /// Bit `i` set when probe `i` was rejected as a temperature outlier.
+    pub rejected_temp: u8,
+    pub rejected_humidity: u8,
+    pub max_gradient_c: i16,
+}
+
+/// Fuses the brood probes of `snapshot` into a single `SensorSnapshot`.
+pub fn fuse(
+    snapshot: &MultiProbeSnapshot,
+    config: &ProbeFusion,
+) -> (SensorSnapshot, FusionReport) {
+    let mut out = snapshot.base.clone();
+    let mut report = FusionReport::default();
+
+    let temps = snapshot.probes.iter().map(|p| p.temp_c.map(|t| t as i32));
+    match fuse_channel(temps, config.outlier_max_dev_c as i32, config.trim) {
+        Some((value, used, rejected)) => {
+            out.brood_temp_c = value as i16;
+            out.missing.remove(SensorFields::BROOD_TEMP);
+            report.temp_probes_used = used;
+            report.rejected_temp = rejected;
+        }
+        None => out.missing.insert(SensorFields::BROOD_TEMP),
+    }
+
+    let humidities = snapshot.probes.iter().map(|p| p.humidity_pct.map(|h| h as i32));
+    match fuse_channel(humidities, config.outlier_max_dev_humidity_pct as i32, config.trim) {
+        Some((value, used, rejected)) => {
+            out.brood_humidity_pct = value as u8;
+            out.missing.remove(SensorFields::BROOD_HUMIDITY);
+            report.humidity_probes_used = used;
+            report.rejected_humidity = rejected;
+        }
+        None => out.missing.insert(SensorFields::BROOD_HUMIDITY),
+    }
+
+    report.max_gradient_c = max_gradient(&snapshot.probes, report.rejected_temp);
+    out.brood_gradient_c = report.max_gradient_c;
+    (out, report)
+}
+
+/// Median-anchored outlier rejection followed by a trimmed mean.
+///
+/// Returns the fused value, the number of readings used and a bitmask of
+/// rejected probe indices.
+fn fuse_channel(
+    readings: impl Iterator<Item = Option<i32>>,
+    max_dev: i32,
+    trim: u8,
+) -> Option<(i32, u8, u8)> {
+    let mut indexed: Vec<(usize, i32), MAX_BROOD_PROBES> = Vec::new();
+    for (index, reading) in readings.enumerate() {
+        if let Some(value) = reading {
+            let _ = indexed.push((index, value));
+        }
+    }
+    if indexed.is_empty() {
+        return None;
+    }
+
+    let mut sorted: Vec<i32, MAX_BROOD_PROBES> = indexed.iter().map(|(_, v)| *v).collect();
+    sorted.sort_unstable();
+    let median = sorted[sorted.len() / 2];
+    // A negative deviation would reject the median too and leave nothing.
+    let max_dev = max_dev.max(0);
+
+    let mut rejected = 0u8;
+    let mut kept: Vec<i32, MAX_BROOD_PROBES> = Vec::new();
+    for (index, value) in indexed.iter() {
+        if (value - median).abs() > max_dev {
+            rejected |= 1 << index;
+        } else {
+            let _ = kept.push(*value);
+        }
+    }
+    kept.sort_unstable();
+
+    let trim = trim as usize;
+    let window = if kept.len() > 2 * trim {
+        &kept[trim..kept.len() - trim]
+    } else {
+        &kept[..]
+    };
+    let sum: i32 = window.iter().sum();
+    let n = window.len() as i32;
+    // Round half away from zero.
+    let mean = (2 * sum + n * sum.signum()) / (2 * n);
+    Some((mean, window.len() as u8, rejected))
+}
+
+/// Largest temperature change per frame slot between neighbouring probes.
+fn max_gradient(probes: &[BroodProbe], rejected: u8) -> i16 {
+    let mut points: Vec<(u8, i32), MAX_BROOD_PROBES> = Vec::new();
+    for (index, probe) in probes.iter().enumerate() {
+        if rejected & (1 << index) != 0 {
+            continue;
+        }
+        if let Some(temp) = probe.temp_c {
+            let _ = points.push((probe.position.frame, temp as i32));
+        }
+    }
+    points.sort_unstable_by_key(|(frame, _)| *frame);
+
+    points
+        .windows(2)
+        .map(|pair| {
+            let frames = (pair[1].0 - pair[0].0).max(1) as i32;
+            (pair[1].1 - pair[0].1).abs() / frames
+        })
+        .max()
+        .unwrap_or(0)
+        .min(i16::MAX as i32) as i16
+}
@@ -0,0 +1,188 @@
+use serde::{Deserialize, Serialize};
+
+use crate::sensor::SensorSnapshot;
+use crate::timebase::{TickCounter, MS_PER_DAY, MS_PER_HOUR};
+
+/// Hour-of-day slots in the return profile.
+pub const HOMING_PROFILE_SLOTS: usize = 24;
+
+/// Baselines are kept in 1/256 percent.
+const FRAC_BITS: u32 = 8;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct HomingConfig {
+    /// Each sample moves a baseline by 1/2^shift of its error; values
+    /// above 31 act as 31.
+    pub baseline_shift: u8,
+    /// Samples an hour slot needs before it replaces the global baseline.
+    pub min_slot_samples: u16,
+    /// Return shortfall against the expected delta that counts as a deficit.
+    pub deficit_pct: i16,
+    /// Mortality rise over its baseline that must accompany the deficit.
+    pub mortality_rise_pct: u8,
+    /// Consecutive correlated samples before an incident is raised.
+    pub confirm_samples: u16,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum HomingState {
+    Normal,
+    Suspicious,
+    /// Latched until `clear_incident`; forces observation-only.
+    ExposureIncident,
+}
+
+/// What the detector saw when it raised an exposure incident.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ExposureEvidence {
+    pub onset_tick: u64,
+    pub onset_utc_ms: Option<i64>,
+    pub expected_return_delta_pct: i16,
+    pub observed_return_delta_pct: i16,
+    pub baseline_mortality_pct: u8,
+    pub observed_mortality_pct: u8,
+    /// Worst shortfall seen since onset.
+    pub peak_deficit_pct: i16,
+    pub samples: u32,
+}
+
+/// Flags sub-lethal exposure from foragers failing to return.
+///
+/// Return deltas swing with the time of day, so each reading is compared with
+/// its hour's learned profile, falling back to a global baseline until the
+/// slot has enough history. A deficit only counts when daily mortality rises
+/// with it; baselines stop learning while the hive looks suspicious so an
+/// unfolding incident cannot become the new normal.
+#[derive(Clone, Debug)]
+pub struct HomingDetector {
+    global_return: Option<i32>,
+    profile: [i32; HOMING_PROFILE_SLOTS],
+    profile_samples: [u16; HOMING_PROFILE_SLOTS],
+    mortality: Option<i32>,
+    streak: u16,
+    state: HomingState,
+    evidence: Option<ExposureEvidence>,
+}
+
+impl HomingDetector {
+    pub fn new() -> Self {
+        Self {
+            global_return: None,
+            profile: [0; HOMING_PROFILE_SLOTS],
+            profile_samples: [0; HOMING_PROFILE_SLOTS],
+            mortality: None,
+            streak: 0,
+            state: HomingState::Normal,
+            evidence: None,
+        }
+    }
+
+    pub fn observe(
+        &mut self,
+        tick: TickCounter,
+        sensors: &SensorSnapshot,
+        config: &HomingConfig,
+    ) {
+        let slot = hour_slot(tick);
+        let observed = sensors.forager_return_delta_pct;
+        let mortality = sensors.daily_mortality_pct;
+
+        let (Some(global), Some(base_mortality)) = (self.global_return, self.mortality) else {
+            self.global_return = Some((observed as i32) << FRAC_BITS);
+            self.mortality = Some((mortality as i32) << FRAC_BITS);
+            return;
+        };
+        let expected = if self.profile_samples[slot] >= config.min_slot_samples {
+            self.profile[slot]
+        } else {
+            global
+        };
+        let expected_pct = (expected >> FRAC_BITS) as i16;
+        let base_mortality_pct = (base_mortality >> FRAC_BITS).clamp(0, u8::MAX as i32) as u8;
+
+        let deficit = expected_pct.saturating_sub(observed);
+        let correlated = deficit >= config.deficit_pct
+            && mortality.saturating_sub(base_mortality_pct) >= config.mortality_rise_pct;
+
+        if let Some(evidence) = self.evidence.as_mut() {
+            evidence.samples = evidence.samples.saturating_add(1);
+            evidence.peak_deficit_pct = evidence.peak_deficit_pct.max(deficit);
+            return;
+        }
+
+        if !correlated {
+            self.streak = 0;
+            self.state = HomingState::Normal;
+            self.learn(slot, observed, mortality, config);
+            return;
+        }
+
+        self.streak = self.streak.saturating_add(1);
+        self.state = HomingState::Suspicious;
+        if self.streak >= config.confirm_samples {
+            self.state = HomingState::ExposureIncident;
+            self.evidence = Some(ExposureEvidence {
+                onset_tick: tick.ticks(),
+                onset_utc_ms: tick.utc_ms(),
+                expected_return_delta_pct: expected_pct,
+                observed_return_delta_pct: observed,
+                baseline_mortality_pct: base_mortality_pct,
+                observed_mortality_pct: mortality,
+                peak_deficit_pct: deficit,
+                samples: 1,
+            });
+        }
+    }
+
+    pub fn state(&self) -> HomingState {
+        self.state
+    }
+
+    pub fn is_incident(&self) -> bool {
+        self.state == HomingState::ExposureIncident
+    }
+
+    pub fn evidence(&self) -> Option<&ExposureEvidence> {
+        self.evidence.as_ref()
+    }
+
+    /// Acknowledges an incident, returning its evidence. Baselines are kept.
+    pub fn clear_incident(&mut self) -> Option<ExposureEvidence> {
+        self.state = HomingState::Normal;
+        self.streak = 0;
+        self.evidence.take()
+    }
+
+    fn learn(&mut self, slot: usize, observed: i16, mortality: u8, config: &HomingConfig) {
+        // Shifting an i32 by 32 or more overflows; 31 already freezes it.
+        let shift = config.baseline_shift.min(31) as u32;
+        let observed = (observed as i32) << FRAC_BITS;
+        if let Some(global) = self.global_return.as_mut() {
+            *global += (observed - *global) >> shift;
+        }
+        if let Some(base) = self.mortality.as_mut() {
+            *base += (((mortality as i32) << FRAC_BITS) - *base) >> shift;
+        }
+        if self.profile_samples[slot] == 0 {
+            self.profile[slot] = observed;
+        } else {
+            self.profile[slot] += (observed - self.profile[slot]) >> shift;
+        }
+        self.profile_samples[slot] = self.profile_samples[slot].saturating_add(1);
+    }
+}
+
+impl Default for HomingDetector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// UTC hour when anchored, otherwise hours since boot modulo a day.
+fn hour_slot(tick: TickCounter) -> usize {
+    let ms_of_day = match tick.utc_seconds_of_day() {
+        Some(secs) => secs as u64 * 1_000,
+        None => tick.monotonic_ms() % MS_PER_DAY,
+    };
+    (ms_of_day / MS_PER_HOUR) as usize % HOMING_PROFILE_SLOTS
+}
@@ -1,96 +1,723 @@
 #![no_std]
 
+#[cfg(feature = "std")]
+extern crate std;
+
 pub mod config;
 pub mod limits;
+pub mod duty;
+pub mod quota;
+pub mod slew;
+pub mod yellow;
 pub mod band;
 pub mod sensor;
+pub mod sensor_health;
+pub mod acoustic;
+pub mod weight;
+pub mod homing;
+pub mod season;
+pub mod colony;
+pub mod update;
+pub mod persist;
+pub mod power;
+pub mod fusion;
 pub mod actuator;
+pub mod feeder;
 pub mod controller;
+pub mod lif;
+pub mod telemetry;
+pub mod watchdog;
 pub mod failsafe;
 pub mod timebase;
 pub mod board;
+pub mod drivers;
+
+use heapless::Deque;
 
-use crate::band::{BandState, BioloadState};
+use crate::actuator::{ActuatorCommand, ActuatorKind, ActuatorZone};
+use crate::band::{BandDebouncer, BandState, BandThresholds, BioloadState, BioloadThresholds};
+use crate::colony::{ColonyPhase, ColonyPhaseDetector, PhaseChange};
 use crate::config::ShardConfig;
-use crate::controller::NeuromorphicController;
-use crate::failsafe::FailsafeMode;
+use crate::controller::{NeuromorphicController, StepUsage};
+use crate::duty::{DutyBudget, DutyCycleTracker};
+use crate::feeder::FeederLedger;
+use crate::failsafe::{FailsafeEvent, FailsafeMachine, FailsafeMode, FailsafeReason};
+use crate::fusion::{FusionReport, MultiProbeSnapshot};
+use crate::homing::{ExposureEvidence, HomingDetector, HomingState};
 use crate::limits::ShardLimits;
-use crate::sensor::SensorSnapshot;
+use crate::persist::{Checkpoint, CheckpointCursor, NonVolatileStore};
+use crate::power::{PowerManager, PowerMode, PowerState};
+use crate::quota::{QuotaLedger, QuotaUsage};
+use crate::sensor::{SensorFields, SensorSnapshot};
+use crate::sensor_health::{SensorHealthReport, SensorValidator};
+use crate::slew::SlewLimiter;
+use crate::telemetry::ShardTelemetry;
 use crate::timebase::TickCounter;
+use crate::update::{ConfigUpdateError, ConfigVerifier};
+use crate::watchdog::{DeadlineOutcome, DeadlineWatchdog, MonotonicClock};
+use crate::weight::{WeightEvent, WeightTrendAnalyzer};
+use crate::yellow::{YellowBudgetAction, YellowBudgetTracker};
+
+/// Failsafe transitions buffered between calls to `pop_failsafe_event`.
+pub const FAILSAFE_EVENT_QUEUE: usize = 8;
 
 /// Main shard runtime, designed for periodic stepping in a deterministic loop.
 pub struct HiveShardRuntime<C: NeuromorphicController> {
     config: ShardConfig,
+    config_verifier: Option<ConfigVerifier>,
     limits: ShardLimits,
     controller: C,
+    sensor_validator: SensorValidator,
+    fusion: FusionReport,
+    active_bands: BandThresholds,
+    active_bioload: BioloadThresholds,
+    season_day: Option<u16>,
+    threshold_phase: ColonyPhase,
     band_state: BandState,
+    /// Band from the colony readings alone, before weight floors.
+    colony_band: BandState,
+    band_debounce: BandDebouncer,
     bioload_state: BioloadState,
     tick: TickCounter,
-    failsafe: FailsafeMode,
+    failsafe: FailsafeMachine,
+    failsafe_events: Deque<FailsafeEvent, FAILSAFE_EVENT_QUEUE>,
+    quota: QuotaLedger,
+    last_step: StepUsage,
+    watchdog: DeadlineWatchdog,
+    slew: SlewLimiter,
+    feeder: FeederLedger,
+    duty: DutyCycleTracker,
+    power: PowerManager,
+    yellow: YellowBudgetTracker,
+    weight: WeightTrendAnalyzer,
+    homing: HomingDetector,
+    colony: ColonyPhaseDetector,
+    /// Last entrance reducer opening sent per zone; `None` until one is.
+    reducer_open_pct: [Option<u8>; ActuatorZone::ALL.len()],
+    checkpoints: CheckpointCursor,
+    restored: Option<Checkpoint>,
 }
 
 impl<C: NeuromorphicController> HiveShardRuntime<C> {
     pub fn new(config: ShardConfig, controller: C) -> Self {
-        let limits = config.limits;
+        let limits = config.limits.clone();
+        let active_bands = config.bands.clone();
+        let active_bioload = config.bioload_thresholds.clone();
+        let tick = TickCounter::with_period_ms(config.tick_period_ms);
+        let quota = QuotaLedger::new(&config.quota_profile, &tick);
+        let slew = SlewLimiter::new(&tick);
+        let feeder = FeederLedger::new(&tick);
+        let duty = DutyCycleTracker::new(&config.duty_cycle, &tick);
+        let yellow = YellowBudgetTracker::new(&config.yellow_budget, &tick);
+        let weight = WeightTrendAnalyzer::new(&config.weight_trend, &tick);
+        let colony = ColonyPhaseDetector::new(&tick);
         Self {
             config,
+            config_verifier: None,
             limits,
             controller,
+            sensor_validator: SensorValidator::new(),
+            fusion: FusionReport::default(),
+            active_bands,
+            active_bioload,
+            season_day: None,
+            threshold_phase: ColonyPhase::BroodRearing,
             band_state: BandState::Green,
+            colony_band: BandState::Green,
+            band_debounce: BandDebouncer::new(),
             bioload_state: BioloadState::Nominal,
-            tick: TickCounter::new(),
-            failsafe: FailsafeMode::Normal,
+            tick,
+            failsafe: FailsafeMachine::new(),
+            failsafe_events: Deque::new(),
+            quota,
+            last_step: StepUsage::default(),
+            watchdog: DeadlineWatchdog::new(),
+            slew,
+            feeder,
+            duty,
+            power: PowerManager::new(),
+            yellow,
+            weight,
+            homing: HomingDetector::new(),
+            colony,
+            reducer_open_pct: [None; ActuatorZone::ALL.len()],
+            checkpoints: CheckpointCursor::default(),
+            restored: None,
         }
     }
 
+    /// Like `new`, resuming from the newest valid checkpoint in `store`.
+    ///
+    /// Failsafe mode, band, yellow-band time and quota usage carry over, the
+    /// usage charged afresh at boot. A hive that went down uncleanly while
+    /// red comes back in observation-only regardless of its recorded mode.
+    pub fn restore<S: NonVolatileStore>(
+        config: ShardConfig,
+        controller: C,
+        store: &mut S,
+    ) -> Result<Self, S::Error> {
+        let mut runtime = Self::new(config, controller);
+        let (checkpoint, cursor) = CheckpointCursor::scan(store)?;
+        runtime.checkpoints = cursor;
+        if let Some(checkpoint) = checkpoint {
+            runtime.resume(&checkpoint);
+            runtime.restored = Some(checkpoint);
+        }
+        Ok(runtime)
+    }
+
     /// Called every control period with current sensors; returns actuator outputs.
     pub fn step(
         &mut self,
         sensors: &SensorSnapshot,
+    ) -> crate::actuator::ActuatorCommandFrame {
+        self.step_inner(sensors, None)
+    }
+
+    /// Like `step`, timing the controller against `clock`. Late outputs are
+    /// still used until `max_consecutive_overruns` is reached, after which the
+    /// runtime falls back to observation-only.
+    pub fn step_budgeted<K: MonotonicClock>(
+        &mut self,
+        sensors: &SensorSnapshot,
+        clock: &K,
+    ) -> crate::actuator::ActuatorCommandFrame {
+        self.step_inner(sensors, Some(clock))
+    }
+
+    fn step_inner(
+        &mut self,
+        sensors: &SensorSnapshot,
+        clock: Option<&dyn MonotonicClock>,
     ) -> crate::actuator::ActuatorCommandFrame {
         self.tick.increment();
-        self.band_state = self.band_state.evaluate(sensors, &self.config.bands);
+        let sensors = self
+            .sensor_validator
+            .validate(sensors, &self.config.sensor_plausibility);
+        self.slew.observe(self.tick, &sensors);
+        if !self.sensor_health().faulted().contains(SensorFields::HIVE_WEIGHT) {
+            self.weight
+                .observe(self.tick, &sensors, &self.config.weight_trend);
+        }
+        let homing_inputs = SensorFields::FORAGER_RETURN | SensorFields::DAILY_MORTALITY;
+        if !self.sensor_health().faulted().intersects(homing_inputs) {
+            self.homing.observe(self.tick, &sensors, &self.config.homing);
+        }
+        self.colony.observe(
+            self.tick,
+            &sensors,
+            self.weight.net_kg_x10_per_day(),
+            &self.config.colony_phase,
+        );
+
+        let mut commands = self.command(&sensors, clock);
+        self.hold_entrance_floor(&mut commands);
+        // Duty cycling runs first so the slew limiter records the setpoints
+        // that actually go out.
+        self.duty.enforce(&mut commands, self.tick, &self.limits);
+        self.slew.limit(&mut commands, self.tick, &sensors, &self.limits);
+        self.feeder.record(&commands, self.tick);
+        commands
+    }
+
+    /// Like `step`, for hives carrying several brood probes: the probes are
+    /// fused into a single brood temperature, humidity and gradient first.
+    pub fn step_multi_probe(
+        &mut self,
+        snapshot: &MultiProbeSnapshot,
+    ) -> crate::actuator::ActuatorCommandFrame {
+        let (sensors, report) = fusion::fuse(snapshot, &self.config.probe_fusion);
+        self.fusion = report;
+        self.step(&sensors)
+    }
+
+    fn command(
+        &mut self,
+        sensors: &SensorSnapshot,
+        clock: Option<&dyn MonotonicClock>,
+    ) -> crate::actuator::ActuatorCommandFrame {
+        self.refresh_thresholds();
+        // A broken band input says nothing about the colony: the band holds
+        // and the fault trips the failsafe below as `SensorFault`.
+        let band_inputs_faulted = self
+            .sensor_health()
+            .faulted()
+            .intersects(SensorFields::BAND_INPUTS);
+        if !band_inputs_faulted {
+            let raw_band = self.colony_band.evaluate(sensors, &self.active_bands);
+            self.colony_band =
+                self.band_debounce
+                    .update(self.colony_band, raw_band, &self.active_bands);
+        }
+        self.band_state = self.colony_band.escalate(self.weight.band_floor(self.tick));
         self.bioload_state =
-            BioloadState::from_snapshot(sensors, &self.config.bioload_thresholds);
+            BioloadState::from_snapshot(sensors, &self.active_bioload)
+                .escalate(self.weight.bioload_floor());
+        // Weight floors are reported but neither charge the yellow budget nor
+        // move the failsafe: a hive short of stores needs its feeder, which
+        // observation-only would switch off.
+        let yellow_ran_out = self.yellow.record(self.tick, self.colony_band);
 
-        if self.band_state.is_red() || self.bioload_state.is_critical() {
-            self.failsafe = FailsafeMode::ObservationOnly;
+        if self.band_state.is_red() {
+            self.trip_failsafe(FailsafeReason::RedBand);
+        } else if self.bioload_state.is_critical() {
+            self.trip_failsafe(FailsafeReason::CriticalBioload);
+        } else if self.homing.is_incident() {
+            self.trip_failsafe(FailsafeReason::ExposureIncident);
+        } else if band_inputs_faulted {
+            self.trip_failsafe(FailsafeReason::SensorFault);
+        } else if self.yellow_budget_exhausted()
+            && self.config.yellow_budget.on_exhausted == YellowBudgetAction::ObservationOnly
+        {
+            self.trip_failsafe(FailsafeReason::YellowBudgetExhausted);
+        } else {
+            let event = self.failsafe.update(
+                self.tick.ticks(),
+                self.colony_band,
+                &self.config.failsafe,
+            );
+            self.push_failsafe_event(event);
+        }
+        if yellow_ran_out && self.config.yellow_budget.on_exhausted == YellowBudgetAction::Notify {
+            let mode = self.failsafe.mode();
+            self.push_failsafe_event(Some(FailsafeEvent {
+                tick: self.tick.ticks(),
+                from: mode,
+                to: mode,
+                reason: FailsafeReason::YellowBudgetExhausted,
+            }));
         }
 
-        if self.failsafe.is_observation_only() {
+        let mode = self.failsafe.mode();
+        if mode.is_observation_only() {
             return crate::actuator::ActuatorCommandFrame::observation_only();
         }
 
-        let quota_ok = self
-            .limits
-            .check_and_debit_quota(self.tick, sensors, &self.config.quota_profile);
+        if let Some(power) = &self.config.power {
+            if !self.power.admits_inference(self.tick, power) {
+                return crate::actuator::ActuatorCommandFrame::observation_only();
+            }
+        }
 
-        if !quota_ok {
-            self.failsafe = FailsafeMode::ObservationOnly;
+        if let Err(violation) = self.limits.check_and_debit_quota(
+            &mut self.quota,
+            self.tick,
+            &self.config.quota_profile,
+        ) {
+            self.trip_failsafe(FailsafeReason::Quota(violation));
             return crate::actuator::ActuatorCommandFrame::observation_only();
         }
 
+        let started_us = clock.map(|c| c.now_us());
         let mut commands = self.controller.step_neuromorphic(sensors);
+        self.last_step = self.controller.step_usage();
+
+        let mut outcome = DeadlineOutcome::OnTime;
+        if let (Some(clock), Some(started_us)) = (clock, started_us) {
+            let elapsed_us = clock.now_us().saturating_sub(started_us);
+            if self.last_step.wall_time_us == 0 {
+                self.last_step.wall_time_us = elapsed_us.min(u32::MAX as u64) as u32;
+            }
+            outcome = self.watchdog.record(elapsed_us, &self.config.watchdog);
+        }
 
+        // The step ran either way, so it is charged before any trip.
+        let debit = self.limits.debit_step_usage(
+            &mut self.quota,
+            self.tick,
+            &self.last_step.quota_usage(),
+            &self.config.quota_profile,
+        );
+        if outcome == DeadlineOutcome::Tripped {
+            self.trip_failsafe(FailsafeReason::DeadlineOverrun);
+            return crate::actuator::ActuatorCommandFrame::observation_only();
+        }
+        if let Err(violation) = debit {
+            self.trip_failsafe(FailsafeReason::Quota(violation));
+            return crate::actuator::ActuatorCommandFrame::observation_only();
+        }
+
+        let caps = &self.config.actuation_caps;
         self.limits
-            .enforce_actuation_caps(&mut commands, &self.config.actuation_caps);
+            .enforce_actuation_caps(&mut commands, caps, self.tick, sensors);
+        self.feeder.limit(&mut commands, self.tick, caps);
+        if mode.is_probationary() {
+            let caps = &self.config.failsafe.probation_caps;
+            self.limits
+                .enforce_actuation_caps(&mut commands, caps, self.tick, sensors);
+            self.feeder.limit(&mut commands, self.tick, caps);
+        }
+        self.colony
+            .restrict(&mut commands, sensors, &self.config.colony_phase);
+        if let Some(power) = &self.config.power {
+            self.power.shed(&mut commands, power);
+        }
 
         commands
     }
 
+    /// Installs the operator key that config updates must be signed with,
+    /// along with the version of the config the runtime was started with.
+    ///
+    /// Checkpoints record the version that was running but not the config
+    /// itself, so an update applied before a reboot has to be sent again.
+    /// Until it is, nothing older than that version is accepted.
+    pub fn provision_update_key(
+        &mut self,
+        public_key: &[u8; 32],
+        installed_version: u64,
+    ) -> Result<(), ConfigUpdateError> {
+        let checkpointed = self.restored.as_ref().and_then(|c| c.config_version);
+        self.config_verifier =
+            Some(ConfigVerifier::new(public_key, installed_version)?.after_reboot(checkpointed));
+        Ok(())
+    }
+
+    /// Version of the running config, once an update key is provisioned.
+    pub fn config_version(&self) -> Option<u64> {
+        self.config_verifier.as_ref().map(|v| v.installed_version())
+    }
+
+    /// Verifies and installs a signed `SignedConfig` payload, returning its
+    /// version. Nothing changes unless every check passes, and the swap takes
+    /// effect from the next step. Band, failsafe, quota and budget history
+    /// carry over so an update cannot reset them.
+    pub fn apply_config_update(
+        &mut self,
+        payload: &[u8],
+        signature: &[u8; 64],
+    ) -> Result<u64, ConfigUpdateError> {
+        let verifier = self
+            .config_verifier
+            .as_mut()
+            .ok_or(ConfigUpdateError::NotProvisioned)?;
+        let update = verifier.verify(payload, signature)?;
+        if !update::same_geometry(&self.config, &update.config) {
+            return Err(ConfigUpdateError::GeometryChanged);
+        }
+        verifier.commit(update.version);
+
+        self.config = update.config;
+        self.limits = self.config.limits.clone();
+        self.yellow.set_budget(&self.config.yellow_budget, &self.tick);
+        self.power.set_policy(self.config.power.as_ref());
+        self.resolve_thresholds();
+        Ok(update.version)
+    }
+
+    /// Writes a checkpoint if `checkpoint_interval_ticks` have passed since
+    /// the last one, or straight away once the band or failsafe mode has
+    /// changed; returns whether it did.
+    pub fn checkpoint<S: NonVolatileStore>(&mut self, store: &mut S) -> Result<bool, S::Error> {
+        let now = self.tick.ticks();
+        let interval = self.config.persist.checkpoint_interval_ticks as u64;
+        let unchanged =
+            self.checkpoints.last_state == Some((self.band_state, self.failsafe.mode()));
+        if unchanged
+            && self
+                .checkpoints
+                .last_tick
+                .is_some_and(|last| now.saturating_sub(last) < interval)
+        {
+            return Ok(false);
+        }
+        let checkpoint = self.snapshot_checkpoint(false);
+        self.checkpoints.write(store, &checkpoint)?;
+        Ok(true)
+    }
+
+    /// Writes the final checkpoint of an orderly shutdown.
+    pub fn shutdown<S: NonVolatileStore>(&mut self, store: &mut S) -> Result<(), S::Error> {
+        let checkpoint = self.snapshot_checkpoint(true);
+        self.checkpoints.write(store, &checkpoint)
+    }
+
+    /// Checkpoint the runtime resumed from in `restore`, if any.
+    pub fn restored_checkpoint(&self) -> Option<&Checkpoint> {
+        self.restored.as_ref()
+    }
+
+    /// Health of the readings in the most recent snapshot. A faulted band
+    /// input trips `FailsafeReason::SensorFault` rather than a band reason.
+    pub fn sensor_health(&self) -> &SensorHealthReport {
+        self.sensor_validator.report()
+    }
+
+    /// Outcome of the most recent probe fusion in `step_multi_probe`.
+    pub fn fusion_report(&self) -> &FusionReport {
+        &self.fusion
+    }
+
+    /// Band thresholds in force, after any seasonal interpolation.
+    pub fn active_band_thresholds(&self) -> &BandThresholds {
+        &self.active_bands
+    }
+
+    pub fn active_bioload_thresholds(&self) -> &BioloadThresholds {
+        &self.active_bioload
+    }
+
     pub fn band_state(&self) -> BandState {
         self.band_state
     }
 
+    /// Seconds of yellow-band time left in the rolling 72-hour budget.
+    pub fn yellow_budget_remaining_secs(&self) -> u32 {
+        self.yellow.remaining_secs(self.tick)
+    }
+
+    pub fn yellow_budget_exhausted(&self) -> bool {
+        self.yellow.is_exhausted(self.tick)
+    }
+
     pub fn bioload_state(&self) -> BioloadState {
         self.bioload_state
     }
 
+    pub fn weight_trend(&self) -> &WeightTrendAnalyzer {
+        &self.weight
+    }
+
+    /// Oldest unread weight event, if any.
+    pub fn pop_weight_event(&mut self) -> Option<WeightEvent> {
+        self.weight.pop_event()
+    }
+
+    /// On-time used and left for `actuator` in `zone`; `None` for entrance
+    /// reducers and vent flaps, which are not duty-cycled.
+    pub fn duty_cycle_budget(
+        &self,
+        zone: ActuatorZone,
+        actuator: ActuatorKind,
+    ) -> Option<DutyBudget> {
+        self.duty.budget(zone, actuator, self.tick, &self.limits)
+    }
+
+    /// Syrup pumped by the feeders over the last 24 h.
+    pub fn feeder_syrup_ml_today(&self) -> u32 {
+        self.feeder.dispensed_ml_today(self.tick.ticks())
+    }
+
+    pub fn colony_phase(&self) -> ColonyPhase {
+        self.colony.phase()
+    }
+
+    /// Oldest unread colony phase change, if any.
+    pub fn pop_phase_change(&mut self) -> Option<PhaseChange> {
+        self.colony.pop_change()
+    }
+
+    pub fn homing_state(&self) -> HomingState {
+        self.homing.state()
+    }
+
+    /// Evidence for the active suspected exposure incident, if any.
+    pub fn exposure_evidence(&self) -> Option<&ExposureEvidence> {
+        self.homing.evidence()
+    }
+
+    /// Operator acknowledgement of an exposure incident. The runtime then
+    /// re-arms through the usual observation dwell and probation.
+    pub fn clear_exposure_incident(&mut self) -> Option<ExposureEvidence> {
+        self.homing.clear_incident()
+    }
+
+    pub fn tick(&self) -> TickCounter {
+        self.tick
+    }
+
+    /// Latest battery reading; ignored unless `ShardConfig::power` is set.
+    pub fn update_power(&mut self, state: PowerState) {
+        if let Some(power) = &self.config.power {
+            self.power.update(state, power);
+        }
+    }
+
+    pub fn power_mode(&self) -> PowerMode {
+        self.power.mode()
+    }
+
+    /// Anchors the runtime clock to UTC, e.g. after a gateway time sync.
+    pub fn anchor_utc(&mut self, unix_ms: i64) {
+        self.tick.anchor_utc(unix_ms);
+    }
+
     pub fn failsafe_mode(&self) -> FailsafeMode {
-        self.failsafe
+        self.failsafe.mode()
+    }
+
+    pub fn failsafe_reason(&self) -> Option<FailsafeReason> {
+        self.failsafe.reason()
+    }
+
+    /// Oldest unread failsafe transition, if any.
+    pub fn pop_failsafe_event(&mut self) -> Option<FailsafeEvent> {
+        self.failsafe_events.pop_front()
     }
 
     pub fn limits(&self) -> &ShardLimits {
         &self.limits
     }
+
+    pub fn quota(&self) -> &QuotaLedger {
+        &self.quota
+    }
+
+    pub fn watchdog(&self) -> &DeadlineWatchdog {
+        &self.watchdog
+    }
+
+    /// Usage reported by the controller for its most recent step.
+    pub fn last_step_usage(&self) -> StepUsage {
+        self.last_step
+    }
+
+    pub fn telemetry(&self) -> ShardTelemetry {
+        let now = self.tick.ticks();
+        ShardTelemetry {
+            tick: now,
+            band: self.band_state,
+            bioload: self.bioload_state,
+            failsafe: self.failsafe.mode(),
+            failsafe_reason: self.failsafe.reason(),
+            last_step: self.last_step,
+            ops_in_window: self.quota.ops_in_window(now),
+            inferences_in_last_minute: self.quota.inferences_in_last_minute(now),
+            spikes_in_window: self.quota.spikes_in_window(now),
+            energy_mj_in_window: self.quota.energy_mj_in_window(now),
+            yellow_budget_remaining_secs: self.yellow_budget_remaining_secs(),
+            yellow_budget_exhausted: self.yellow_budget_exhausted(),
+            deadline_overruns: self.watchdog.total_overruns(),
+            duty_cycle: self.duty.report(self.tick, &self.limits),
+            weight_net_kg_x10_per_day: self.weight.net_kg_x10_per_day(),
+            nectar_flow: self.weight.in_nectar_flow(),
+            homing: self.homing.state(),
+            colony_phase: self.colony.phase(),
+            power_mode: self.power.mode(),
+            battery_soc_pct: self.power.state().map(|s| s.battery_soc_pct),
+        }
+    }
+
+    /// Re-resolves thresholds when the UTC day or the colony phase changes.
+    fn refresh_thresholds(&mut self) {
+        let day = self.tick.utc_day_of_year();
+        let phase = self.colony.phase();
+        if day == self.season_day && phase == self.threshold_phase {
+            return;
+        }
+        self.season_day = day;
+        self.threshold_phase = phase;
+        self.resolve_thresholds();
+    }
+
+    /// Brood rearing uses the seasonal profile, or the static `bands` and
+    /// `bioload_thresholds` until the clock is anchored; the other phases
+    /// substitute their own band thresholds.
+    fn resolve_thresholds(&mut self) {
+        let (mut bands, bioload) = match (self.season_day, self.config.seasonal.as_ref()) {
+            (Some(day), Some(seasonal)) => seasonal.resolve(day),
+            _ => None,
+        }
+        .unwrap_or_else(|| {
+            (
+                self.config.bands.clone(),
+                self.config.bioload_thresholds.clone(),
+            )
+        });
+        match self.threshold_phase {
+            ColonyPhase::BroodRearing => {}
+            ColonyPhase::Broodless => bands = self.config.colony_phase.broodless_bands.clone(),
+            ColonyPhase::WinterCluster => bands = self.config.colony_phase.cluster_bands.clone(),
+        }
+        self.active_bands = bands;
+        self.active_bioload = bioload;
+    }
+
+    fn snapshot_checkpoint(&self, clean: bool) -> Checkpoint {
+        let now = self.tick.ticks();
+        Checkpoint {
+            sequence: self.checkpoints.sequence,
+            tick: now,
+            failsafe: self.failsafe.mode(),
+            failsafe_reason: self.failsafe.reason(),
+            band: self.band_state,
+            clean,
+            yellow_used_ticks: self.yellow.used_ticks(self.tick),
+            ops_in_window: self.quota.ops_in_window(now),
+            usage_in_window: QuotaUsage {
+                inferences: self.quota.inferences_in_last_minute(now),
+                spikes: self.quota.spikes_in_window(now),
+                energy_mj: self.quota.energy_mj_in_window(now),
+            },
+            config_version: match &self.config_verifier {
+                Some(verifier) => Some(verifier.newest_version()),
+                None => self.restored.as_ref().and_then(|c| c.config_version),
+            },
+        }
+    }
+
+    fn resume(&mut self, checkpoint: &Checkpoint) {
+        let now = self.tick.ticks();
+        self.band_state = checkpoint.band;
+        self.colony_band = checkpoint.band;
+        self.yellow.restore(self.tick, checkpoint.yellow_used_ticks);
+        self.quota
+            .restore(now, checkpoint.ops_in_window, &checkpoint.usage_in_window);
+        match checkpoint.resume_mode() {
+            (FailsafeMode::ObservationOnly, reason) => {
+                self.trip_failsafe(reason.unwrap_or(FailsafeReason::RedBand));
+            }
+            (mode, reason) => self.failsafe.restore(now, mode, reason),
+        }
+    }
+
+    /// Reopens any entrance reducer held below the envelope's floor, whatever
+    /// path produced the frame: a reducer closed for the night must not stay
+    /// closed into flight hours because the runtime stopped actuating. A
+    /// reducer whose position is unknown is opened once flight hours begin.
+    ///
+    /// A frame too full of repeated channels to take the reducer trips
+    /// `FailsafeReason::FrameOverflow` and is replaced by the floor alone.
+    fn hold_entrance_floor(&mut self, frame: &mut crate::actuator::ActuatorCommandFrame) {
+        let Some(entrance) = &self.config.actuation_caps.entrance else {
+            return;
+        };
+        for channel in frame.channels.iter() {
+            if let ActuatorCommand::EntranceReducer { open_pct } = channel.command {
+                self.reducer_open_pct[channel.zone.index()] = Some(open_pct);
+            }
+        }
+        let floor = entrance.min_open_pct_at(&self.tick);
+        for zone in ActuatorZone::ALL {
+            let held = self.reducer_open_pct[zone.index()];
+            let below = match held {
+                Some(open_pct) => open_pct < floor,
+                None => zone == ActuatorZone::Entrance && entrance.in_flight_hours(&self.tick),
+            };
+            if !below {
+                continue;
+            }
+            let command = ActuatorCommand::EntranceReducer { open_pct: floor };
+            if !frame.set(zone, command) {
+                self.trip_failsafe(FailsafeReason::FrameOverflow);
+                *frame = crate::actuator::ActuatorCommandFrame::observation_only();
+                return self.hold_entrance_floor(frame);
+            }
+            self.reducer_open_pct[zone.index()] = Some(floor);
+        }
+    }
+
+    fn trip_failsafe(&mut self, reason: FailsafeReason) {
+        let event = self.failsafe.trip(self.tick.ticks(), reason);
+        self.push_failsafe_event(event);
+    }
+
+    fn push_failsafe_event(&mut self, event: Option<FailsafeEvent>) {
+        if let Some(event) = event {
+            if self.failsafe_events.is_full() {
+                self.failsafe_events.pop_front();
+            }
+            let _ = self.failsafe_events.push_back(event);
+        }
+    }
 }
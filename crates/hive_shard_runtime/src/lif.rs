@@ -0,0 +1,360 @@
+//! Fixed-point leaky integrate-and-fire reference controller.
+//!
+//! A two-layer feed-forward spiking network: sensor readings are encoded as
+//! input spike trains, integrated by a hidden LIF layer, and three output LIF
+//! neurons (heater, fan, LED) are decoded by spike count. All state is Q8.8
+//! fixed point and fits in static memory.
+
+use heapless::Vec;
+
+use crate::actuator::ActuatorCommandFrame;
+use crate::controller::{NeuromorphicController, StepUsage};
+use crate::sensor::{SensorSnapshot, SENSOR_FIELD_COUNT};
+
+pub const MAX_LIF_INPUTS: usize = SENSOR_FIELD_COUNT;
+pub const MAX_LIF_HIDDEN: usize = 32;
+/// Output neurons, in order: heater, fan, LED.
+pub const LIF_OUTPUTS: usize = 3;
+
+const BLOB_MAGIC: &[u8; 4] = b"LIF1";
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum LifBlobError {
+    BadMagic,
+    Truncated,
+    /// Bytes left over after the weights the header's neuron counts call for.
+    TrailingBytes,
+    TooManyNeurons,
+    BadEncoding,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum SpikeEncoding {
+    /// Spike probability per timestep proportional to the normalized reading.
+    Rate,
+    /// One spike per step, earlier for larger readings.
+    Latency,
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct InputRange {
+    pub min: i16,
+    pub max: i16,
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct LifParams {
+    pub timesteps: u8,
+    /// Firing threshold in Q8.8.
+    pub threshold: i32,
+    /// Membrane decays by `v >> leak_shift` every timestep.
+    pub leak_shift: u8,
+    pub refractory_steps: u8,
+    pub encoding: SpikeEncoding,
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct LifDecoder {
+    pub heater_min_celsius: i16,
+    pub heater_max_celsius: i16,
+    pub fan_max_duty_pct: u8,
+    pub led_max_lux: u32,
+}
+
+/// Per-event energy costs used to estimate `StepUsage::energy_uj`.
+#[derive(Copy, Clone, Debug)]
+pub struct LifEnergyModel {
+    pub nj_per_spike: u32,
+    pub nj_per_synaptic_op: u32,
+}
+
+impl Default for LifEnergyModel {
+    /// Rough figures for a Cortex-M4 class MCU at 64 MHz.
+    fn default() -> Self {
+        Self {
+            nj_per_spike: 20,
+            nj_per_synaptic_op: 5,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, Default)]
+struct LifNeuron {
+    v: i32,
+    refractory: u8,
+}
+
+impl LifNeuron {
+    fn integrate(&mut self, current: i32, params: &LifParams) -> bool {
+        if self.refractory > 0 {
+            self.refractory -= 1;
+            return false;
+        }
+        self.v -= self.v >> params.leak_shift;
+        self.v = self.v.saturating_add(current);
+        if self.v >= params.threshold {
+            self.v = 0;
+            self.refractory = params.refractory_steps;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+/// Spike and synaptic-operation counts of the most recent step.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub struct LifActivity {
+    pub input_spikes: u32,
+    pub hidden_spikes: u32,
+    pub output_spikes: u32,
+    pub synaptic_ops: u32,
+}
+
+impl LifActivity {
+    pub fn total_spikes(&self) -> u32 {
+        self.input_spikes + self.hidden_spikes + self.output_spikes
+    }
+}
+
+pub struct LifController {
+    params: LifParams,
+    decoder: LifDecoder,
+    energy: LifEnergyModel,
+    ranges: Vec<InputRange, MAX_LIF_INPUTS>,
+    hidden_len: usize,
+    /// Row-major `[hidden][input]`, Q8.8.
+    w_in: Vec<i16, { MAX_LIF_HIDDEN * MAX_LIF_INPUTS }>,
+    /// Row-major `[output][hidden]`, Q8.8.
+    w_out: Vec<i16, { LIF_OUTPUTS * MAX_LIF_HIDDEN }>,
+    hidden: [LifNeuron; MAX_LIF_HIDDEN],
+    outputs: [LifNeuron; LIF_OUTPUTS],
+    activity: LifActivity,
+}
+
+impl LifController {
+    /// Loads a network from a little-endian blob:
+    ///
+    /// ```text
+    /// "LIF1" n_inputs:u8 n_hidden:u8 timesteps:u8 encoding:u8
+    /// threshold:i32 leak_shift:u8 refractory:u8
+    /// heater_min:i16 heater_max:i16 fan_max:u8 led_max:u32
+    /// n_inputs x (min:i16 max:i16)
+    /// n_hidden x n_inputs x w_in:i16
+    /// 3 x n_hidden x w_out:i16
+    /// ```
+    pub fn from_blob(blob: &[u8]) -> Result<Self, LifBlobError> {
+        let mut r = BlobReader { bytes: blob };
+        if r.take(4)? != BLOB_MAGIC {
+            return Err(LifBlobError::BadMagic);
+        }
+        let inputs = r.u8()? as usize;
+        let hidden_len = r.u8()? as usize;
+        if inputs > MAX_LIF_INPUTS || hidden_len > MAX_LIF_HIDDEN {
+            return Err(LifBlobError::TooManyNeurons);
+        }
+        let timesteps = r.u8()?;
+        let encoding = match r.u8()? {
+            0 => SpikeEncoding::Rate,
+            1 => SpikeEncoding::Latency,
+            _ => return Err(LifBlobError::BadEncoding),
+        };
+        let params = LifParams {
+            timesteps,
+            threshold: r.i32()?,
+            leak_shift: r.u8()?.min(31),
+            refractory_steps: r.u8()?,
+            encoding,
+        };
+        let decoder = LifDecoder {
+            heater_min_celsius: r.i16()?,
+            heater_max_celsius: r.i16()?,
+            fan_max_duty_pct: r.u8()?,
+            led_max_lux: r.u32()?,
+        };
+
+        let mut ranges = Vec::new();
+        for _ in 0..inputs {
+            let range = InputRange {
+                min: r.i16()?,
+                max: r.i16()?,
+            };
+            let _ = ranges.push(range);
+        }
+        let mut w_in = Vec::new();
+        for _ in 0..hidden_len * inputs {
+            let _ = w_in.push(r.i16()?);
+        }
+        let mut w_out = Vec::new();
+        for _ in 0..LIF_OUTPUTS * hidden_len {
+            let _ = w_out.push(r.i16()?);
+        }
+        if !r.bytes.is_empty() {
+            return Err(LifBlobError::TrailingBytes);
+        }
+
+        Ok(Self {
+            params,
+            decoder,
+            energy: LifEnergyModel::default(),
+            ranges,
+            hidden_len,
+            w_in,
+            w_out,
+            hidden: [LifNeuron::default(); MAX_LIF_HIDDEN],
+            outputs: [LifNeuron::default(); LIF_OUTPUTS],
+            activity: LifActivity::default(),
+        })
+    }
+
+    pub fn with_energy_model(mut self, energy: LifEnergyModel) -> Self {
+        self.energy = energy;
+        self
+    }
+
+    pub fn activity(&self) -> LifActivity {
+        self.activity
+    }
+
+    pub fn reset(&mut self) {
+        self.hidden = [LifNeuron::default(); MAX_LIF_HIDDEN];
+        self.outputs = [LifNeuron::default(); LIF_OUTPUTS];
+    }
+
+    /// Normalized input intensities in 0..=255.
+    fn encode(&self, sensors: &SensorSnapshot) -> [u8; MAX_LIF_INPUTS] {
+        let values = sensors.values();
+        let mut out = [0u8; MAX_LIF_INPUTS];
+        for (i, range) in self.ranges.iter().enumerate() {
+            let span = (range.max as i32 - range.min as i32).max(1);
+            let x = (values[i] - range.min as i32).clamp(0, span);
+            out[i] = (x * 255 / span) as u8;
+        }
+        out
+    }
+
+    fn input_spikes(&self, intensities: &[u8; MAX_LIF_INPUTS], t: u32, spikes: &mut [bool]) {
+        let steps = self.params.timesteps.max(1) as u32;
+        for (i, spike) in spikes.iter_mut().enumerate() {
+            let x = intensities[i] as u32;
+            *spike = match self.params.encoding {
+                // Deterministic rate code: spike whenever the accumulated
+                // intensity crosses a multiple of 256.
+                SpikeEncoding::Rate => ((t + 1) * x) / 256 > (t * x) / 256,
+                SpikeEncoding::Latency => x > 0 && t == (255 - x) * steps / 256,
+            };
+        }
+    }
+
+    fn decode(&self, counts: &[u32; LIF_OUTPUTS]) -> ActuatorCommandFrame {
+        let steps = self.params.timesteps.max(1) as u32;
+        let d = &self.decoder;
+        let heater_celsius = if counts[0] == 0 {
+            0
+        } else {
+            let span = (d.heater_max_celsius as i32 - d.heater_min_celsius as i32).max(0);
+            (d.heater_min_celsius as i32 + span * counts[0].min(steps) as i32 / steps as i32)
+                as i16
+        };
+        ActuatorCommandFrame::brood_and_entrance(
+            heater_celsius,
+            (d.fan_max_duty_pct as u32 * counts[1].min(steps) / steps) as u8,
+            (d.led_max_lux as u64 * counts[2].min(steps) as u64 / steps as u64) as u32,
+        )
+    }
+}
+
+impl NeuromorphicController for LifController {
+    fn step_neuromorphic(&mut self, sensors: &SensorSnapshot) -> ActuatorCommandFrame {
+        let intensities = self.encode(sensors);
+        let inputs = self.ranges.len();
+        let hidden_len = self.hidden_len;
+        let mut activity = LifActivity::default();
+        let mut counts = [0u32; LIF_OUTPUTS];
+        let mut in_spikes = [false; MAX_LIF_INPUTS];
+        let mut hidden_spikes = [false; MAX_LIF_HIDDEN];
+
+        for t in 0..self.params.timesteps as u32 {
+            self.input_spikes(&intensities, t, &mut in_spikes[..inputs]);
+            let fired = in_spikes[..inputs].iter().filter(|s| **s).count() as u32;
+            activity.input_spikes += fired;
+            activity.synaptic_ops += fired * hidden_len as u32;
+
+            for (h, spike) in hidden_spikes[..hidden_len].iter_mut().enumerate() {
+                let row = &self.w_in[h * inputs..(h + 1) * inputs];
+                let current: i32 = row
+                    .iter()
+                    .zip(in_spikes.iter())
+                    .filter(|(_, s)| **s)
+                    .map(|(w, _)| *w as i32)
+                    .sum();
+                *spike = self.hidden[h].integrate(current, &self.params);
+            }
+            let fired = hidden_spikes[..hidden_len].iter().filter(|s| **s).count() as u32;
+            activity.hidden_spikes += fired;
+            activity.synaptic_ops += fired * LIF_OUTPUTS as u32;
+
+            for (o, count) in counts.iter_mut().enumerate() {
+                let row = &self.w_out[o * hidden_len..(o + 1) * hidden_len];
+                let current: i32 = row
+                    .iter()
+                    .zip(hidden_spikes.iter())
+                    .filter(|(_, s)| **s)
+                    .map(|(w, _)| *w as i32)
+                    .sum();
+                if self.outputs[o].integrate(current, &self.params) {
+                    *count += 1;
+                }
+            }
+        }
+        activity.output_spikes = counts.iter().sum();
+        self.activity = activity;
+        self.decode(&counts)
+    }
+
+    fn step_usage(&self) -> StepUsage {
+        let a = &self.activity;
+        let nj = a.total_spikes() as u64 * self.energy.nj_per_spike as u64
+            + a.synaptic_ops as u64 * self.energy.nj_per_synaptic_op as u64;
+        StepUsage {
+            spikes: a.total_spikes(),
+            synaptic_ops: a.synaptic_ops,
+            energy_uj: nj.div_ceil(1000).min(u32::MAX as u64) as u32,
+            wall_time_us: 0,
+        }
+    }
+}
+
+struct BlobReader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> BlobReader<'a> {
+    fn take(&mut self, n: usize) -> Result<&'a [u8], LifBlobError> {
+        if self.bytes.len() < n {
+            return Err(LifBlobError::Truncated);
+        }
+        let (head, tail) = self.bytes.split_at(n);
+        self.bytes = tail;
+        Ok(head)
+    }
+
+    fn u8(&mut self) -> Result<u8, LifBlobError> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn i16(&mut self) -> Result<i16, LifBlobError> {
+        let b = self.take(2)?;
+        Ok(i16::from_le_bytes([b[0], b[1]]))
+    }
+
+    fn i32(&mut self) -> Result<i32, LifBlobError> {
+        let b = self.take(4)?;
+        Ok(i32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+    }
+
+    fn u32(&mut self) -> Result<u32, LifBlobError> {
+        let b = self.take(4)?;
+        Ok(u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+    }
+}
@@ -1,8 +1,11 @@
 use serde::{Deserialize, Serialize};
 
-use crate::actuator::ActuatorCommandFrame;
+use heapless::Vec;
+
+use crate::actuator::{ActuatorCommand, ActuatorCommandFrame, ActuatorZone};
+use crate::quota::{QuotaLedger, QuotaUsage, QuotaViolation};
 use crate::sensor::SensorSnapshot;
-use crate::timebase::TickCounter;
+use crate::timebase::{TickCounter, MS_PER_DAY, MS_PER_HOUR, MS_PER_MINUTE, MS_PER_SECOND};
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct ShardLimits {
@@ -12,44 +15,198 @@ pub struct ShardLimits {
     pub max_actuator_duty_cycle_pct: u8,
     pub max_delta_t_c_per_hour: i16,
     pub max_delta_db_per_hour: i16,
+    #[serde(default = "default_max_fan_delta_pct_per_hour")]
+    pub max_fan_delta_pct_per_hour: u8,
+}
+
+/// Fan slew for configs written before it was limited.
+fn default_max_fan_delta_pct_per_hour() -> u8 {
+    20
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct QuotaProfile {
     pub window_ticks: u32,
     pub max_ops_in_window: u32,
+    #[serde(default = "default_max_spikes_in_window")]
+    pub max_spikes_in_window: u32,
+    #[serde(default = "default_max_energy_mj_in_window")]
+    pub max_energy_mj_in_window: u32,
+}
+
+/// Window budgets for configs written before they existed: sixty
+/// inferences of 10 000 spikes and 50 mJ each.
+fn default_max_spikes_in_window() -> u32 {
+    600_000
 }
 
+fn default_max_energy_mj_in_window() -> u32 {
+    3_000
+}
+
+/// Hive-wide caps, optionally tightened per zone. Entrance reducers, vent
+/// flaps and feeders are only driven when their envelope is configured.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct ActuationCaps {
     pub heater_max_celsius: i16,
     pub fan_max_duty_pct: u8,
     pub led_max_lux: u32,
+    #[serde(default)]
+    pub zones: Vec<ZoneCaps, { ActuatorZone::ALL.len() }>,
+    #[serde(default)]
+    pub entrance: Option<EntranceEnvelope>,
+    #[serde(default)]
+    pub vent_flap: Option<VentFlapEnvelope>,
+    #[serde(default)]
+    pub feeder: Option<FeederEnvelope>,
+}
+
+/// Caps for one zone; the lower of these and the hive-wide caps applies.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ZoneCaps {
+    pub zone: ActuatorZone,
+    pub heater_max_celsius: i16,
+    pub fan_max_duty_pct: u8,
+    pub led_max_lux: u32,
+}
+
+/// Entrance reducer limits. Between the local flight hours the reducer
+/// never closes past `flight_min_open_pct`, so foragers are not shut out.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct EntranceEnvelope {
+    pub min_open_pct: u8,
+    pub flight_min_open_pct: u8,
+    /// Local hour flight starts, inclusive.
+    pub flight_start_hour: u8,
+    /// Local hour flight ends, exclusive; may wrap past midnight.
+    pub flight_end_hour: u8,
+    pub utc_offset_min: i16,
+}
+
+impl EntranceEnvelope {
+    /// Whether bees may be flying at `tick`. Assumed so until the clock is
+    /// anchored to UTC.
+    pub fn in_flight_hours(&self, tick: &TickCounter) -> bool {
+        let Some(seconds) = tick.utc_seconds_of_day() else {
+            return true;
+        };
+        let offset_ms = self.utc_offset_min as i64 * MS_PER_MINUTE as i64;
+        let local_ms = (seconds as i64 * MS_PER_SECOND as i64 + offset_ms)
+            .rem_euclid(MS_PER_DAY as i64);
+        let hour = (local_ms / MS_PER_HOUR as i64) as u8;
+        if self.flight_start_hour <= self.flight_end_hour {
+            (self.flight_start_hour..self.flight_end_hour).contains(&hour)
+        } else {
+            hour >= self.flight_start_hour || hour < self.flight_end_hour
+        }
+    }
+
+    /// Narrowest opening allowed at `tick`.
+    pub fn min_open_pct_at(&self, tick: &TickCounter) -> u8 {
+        if self.in_flight_hours(tick) {
+            self.min_open_pct.max(self.flight_min_open_pct)
+        } else {
+            self.min_open_pct
+        }
+    }
+}
+
+/// Ventilation flap limits; a cold brood nest further limits the opening.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct VentFlapEnvelope {
+    pub max_open_pct: u8,
+    pub cold_below_c: i16,
+    pub cold_max_open_pct: u8,
+}
+
+/// Syrup feeder limits. The daily volume is tracked by `FeederLedger`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct FeederEnvelope {
+    pub max_ml_per_period: u16,
+    pub max_ml_per_day: u32,
+}
+
+impl ActuationCaps {
+    /// Effective caps for `zone`.
+    pub fn for_zone(&self, zone: ActuatorZone) -> (i16, u8, u32) {
+        let global = (self.heater_max_celsius, self.fan_max_duty_pct, self.led_max_lux);
+        match self.zones.iter().find(|z| z.zone == zone) {
+            Some(z) => (
+                global.0.min(z.heater_max_celsius),
+                global.1.min(z.fan_max_duty_pct),
+                global.2.min(z.led_max_lux),
+            ),
+            None => global,
+        }
+    }
 }
 
 impl ShardLimits {
+    /// Admits one inference at `tick`, debiting the ops window and the
+    /// per-minute inference budget.
     pub fn check_and_debit_quota(
-        &mut self,
-        _tick: TickCounter,
-        _sensors: &SensorSnapshot,
-        _quota: &QuotaProfile,
-    ) -> bool {
-        true
+        &self,
+        ledger: &mut QuotaLedger,
+        tick: TickCounter,
+        quota: &QuotaProfile,
+    ) -> Result<(), QuotaViolation> {
+        ledger.admit_inference(tick.ticks(), self, quota)
     }
 
+    /// Debits what a completed step actually consumed and checks it against
+    /// the per-period spike and per-inference energy ceilings, and against
+    /// the spike and energy totals allowed over the quota window.
+    pub fn debit_step_usage(
+        &self,
+        ledger: &mut QuotaLedger,
+        tick: TickCounter,
+        usage: &QuotaUsage,
+        quota: &QuotaProfile,
+    ) -> Result<(), QuotaViolation> {
+        ledger.debit_usage(tick.ticks(), usage, self, quota)
+    }
+
+    /// Clamps every channel into `caps`. Reducer and flap channels without
+    /// a configured envelope are dropped so the actuator holds position.
     pub fn enforce_actuation_caps(
         &self,
         frame: &mut ActuatorCommandFrame,
         caps: &ActuationCaps,
+        tick: TickCounter,
+        sensors: &SensorSnapshot,
     ) {
-        if frame.heater_celsius > caps.heater_max_celsius {
-            frame.heater_celsius = caps.heater_max_celsius;
-        }
-        if frame.fan_duty_pct > caps.fan_max_duty_pct {
-            frame.fan_duty_pct = caps.fan_max_duty_pct;
-        }
-        if frame.led_lux > caps.led_max_lux {
-            frame.led_lux = caps.led_max_lux;
+        frame.channels.retain(|c| match c.command {
+            ActuatorCommand::EntranceReducer { .. } => caps.entrance.is_some(),
+            ActuatorCommand::VentFlap { .. } => caps.vent_flap.is_some(),
+            _ => true,
+        });
+        for channel in frame.channels.iter_mut() {
+            let (heater_max, fan_max, led_max) = caps.for_zone(channel.zone);
+            match &mut channel.command {
+                ActuatorCommand::Heater { celsius } => *celsius = (*celsius).min(heater_max),
+                ActuatorCommand::Fan { duty_pct } => *duty_pct = (*duty_pct).min(fan_max),
+                ActuatorCommand::Led { lux } => *lux = (*lux).min(led_max),
+                ActuatorCommand::EntranceReducer { open_pct } => {
+                    if let Some(entrance) = &caps.entrance {
+                        *open_pct = (*open_pct).max(entrance.min_open_pct_at(&tick)).min(100);
+                    }
+                }
+                ActuatorCommand::VentFlap { open_pct } => {
+                    if let Some(flap) = &caps.vent_flap {
+                        let mut max = flap.max_open_pct;
+                        if sensors.brood_temp_c < flap.cold_below_c {
+                            max = max.min(flap.cold_max_open_pct);
+                        }
+                        *open_pct = (*open_pct).min(max);
+                    }
+                }
+                ActuatorCommand::Feeder { syrup_ml } => {
+                    *syrup_ml = caps
+                        .feeder
+                        .as_ref()
+                        .map_or(0, |f| (*syrup_ml).min(f.max_ml_per_period));
+                }
+            }
         }
     }
 }
@@ -0,0 +1,57 @@
+use std::fs::{File, OpenOptions};
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::path::Path;
+
+use super::{NonVolatileStore, CHECKPOINT_LEN};
+
+/// Host-side store keeping the checkpoint slots back to back in one file.
+pub struct FileStore {
+    file: File,
+    slots: u8,
+}
+
+impl FileStore {
+    /// Opens or creates the store file, sizing it for `slots` checkpoints.
+    pub fn open(path: impl AsRef<Path>, slots: u8) -> io::Result<Self> {
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(path)?;
+        let len = slots as u64 * CHECKPOINT_LEN as u64;
+        if file.metadata()?.len() < len {
+            file.set_len(len)?;
+        }
+        Ok(Self { file, slots })
+    }
+}
+
+impl NonVolatileStore for FileStore {
+    type Error = io::Error;
+
+    fn slots(&self) -> u8 {
+        self.slots
+    }
+
+    fn read_slot(
+        &mut self,
+        slot: u8,
+        buf: &mut [u8; CHECKPOINT_LEN],
+    ) -> Result<(), Self::Error> {
+        self.file
+            .seek(SeekFrom::Start(slot as u64 * CHECKPOINT_LEN as u64))?;
+        self.file.read_exact(buf)
+    }
+
+    fn write_slot(
+        &mut self,
+        slot: u8,
+        data: &[u8; CHECKPOINT_LEN],
+    ) -> Result<(), Self::Error> {
+        self.file
+            .seek(SeekFrom::Start(slot as u64 * CHECKPOINT_LEN as u64))?;
+        self.file.write_all(data)?;
+        self.file.sync_data()
+    }
+}
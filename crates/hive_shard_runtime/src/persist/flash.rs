@@ -0,0 +1,71 @@
+use embedded_storage::nor_flash::NorFlash;
+
+use super::{NonVolatileStore, CHECKPOINT_LEN};
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum FlashStoreError<E> {
+    /// The flash's read or write granularity does not divide a checkpoint,
+    /// or a checkpoint does not fit in an erase page.
+    Geometry,
+    Flash(E),
+}
+
+/// One checkpoint slot per erase page, starting at `base`.
+pub struct FlashPageStore<F: NorFlash> {
+    flash: F,
+    base: u32,
+    slots: u8,
+}
+
+impl<F: NorFlash> FlashPageStore<F> {
+    /// `base` must be page aligned and followed by `slots` pages reserved
+    /// for checkpoints.
+    pub fn new(flash: F, base: u32, slots: u8) -> Result<Self, FlashStoreError<F::Error>> {
+        if !CHECKPOINT_LEN.is_multiple_of(F::WRITE_SIZE)
+            || !CHECKPOINT_LEN.is_multiple_of(F::READ_SIZE)
+            || CHECKPOINT_LEN > F::ERASE_SIZE
+            || !(base as usize).is_multiple_of(F::ERASE_SIZE)
+            || base as usize + slots as usize * F::ERASE_SIZE > flash.capacity()
+        {
+            return Err(FlashStoreError::Geometry);
+        }
+        Ok(Self { flash, base, slots })
+    }
+
+    pub fn release(self) -> F {
+        self.flash
+    }
+
+    fn page(&self, slot: u8) -> u32 {
+        self.base + slot as u32 * F::ERASE_SIZE as u32
+    }
+}
+
+impl<F: NorFlash> NonVolatileStore for FlashPageStore<F> {
+    type Error = FlashStoreError<F::Error>;
+
+    fn slots(&self) -> u8 {
+        self.slots
+    }
+
+    fn read_slot(
+        &mut self,
+        slot: u8,
+        buf: &mut [u8; CHECKPOINT_LEN],
+    ) -> Result<(), Self::Error> {
+        let page = self.page(slot);
+        self.flash.read(page, buf).map_err(FlashStoreError::Flash)
+    }
+
+    fn write_slot(
+        &mut self,
+        slot: u8,
+        data: &[u8; CHECKPOINT_LEN],
+    ) -> Result<(), Self::Error> {
+        let page = self.page(slot);
+        self.flash
+            .erase(page, page + F::ERASE_SIZE as u32)
+            .map_err(FlashStoreError::Flash)?;
+        self.flash.write(page, data).map_err(FlashStoreError::Flash)
+    }
+}
@@ -0,0 +1,243 @@
+use serde::{Deserialize, Serialize};
+
+use crate::band::BandState;
+use crate::failsafe::{FailsafeMode, FailsafeReason};
+use crate::quota::QuotaUsage;
+
+pub mod flash;
+#[cfg(feature = "std")]
+pub mod file;
+
+/// Size of one encoded checkpoint, and of every store slot.
+pub const CHECKPOINT_LEN: usize = 64;
+
+const CHECKPOINT_MAGIC: &[u8; 4] = b"HSC1";
+const CRC_OFFSET: usize = CHECKPOINT_LEN - 4;
+const FLAG_CLEAN: u8 = 1 << 0;
+
+/// Non-volatile storage split into equally sized checkpoint slots.
+///
+/// Checkpoints rotate through the slots so each one is rewritten only every
+/// `slots()` checkpoints, spreading erase wear.
+pub trait NonVolatileStore {
+    type Error;
+
+    fn slots(&self) -> u8;
+
+    fn read_slot(
+        &mut self,
+        slot: u8,
+        buf: &mut [u8; CHECKPOINT_LEN],
+    ) -> Result<(), Self::Error>;
+
+    /// Replaces the contents of `slot`, erasing it first where the medium
+    /// requires.
+    fn write_slot(
+        &mut self,
+        slot: u8,
+        data: &[u8; CHECKPOINT_LEN],
+    ) -> Result<(), Self::Error>;
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PersistPolicy {
+    /// Minimum ticks between periodic checkpoints; a band or failsafe mode
+    /// change is checkpointed at once.
+    pub checkpoint_interval_ticks: u32,
+}
+
+/// Runtime state that survives a reboot.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Checkpoint {
+    /// Increments with every checkpoint written; the highest valid one wins.
+    pub sequence: u32,
+    pub tick: u64,
+    pub failsafe: FailsafeMode,
+    pub failsafe_reason: Option<FailsafeReason>,
+    pub band: BandState,
+    /// Written by an orderly shutdown rather than a periodic checkpoint.
+    pub clean: bool,
+    pub yellow_used_ticks: u32,
+    pub ops_in_window: u32,
+    pub usage_in_window: QuotaUsage,
+    /// Newest config version applied, even when a reboot has since brought
+    /// back an older built-in config.
+    pub config_version: Option<u64>,
+}
+
+impl Checkpoint {
+    /// Fixed little-endian layout closed by a CRC-32 of the preceding bytes.
+    pub fn encode(&self) -> [u8; CHECKPOINT_LEN] {
+        let mut buf = [0u8; CHECKPOINT_LEN];
+        buf[0..4].copy_from_slice(CHECKPOINT_MAGIC);
+        buf[4..8].copy_from_slice(&self.sequence.to_le_bytes());
+        buf[8..16].copy_from_slice(&self.tick.to_le_bytes());
+        buf[16] = mode_code(self.failsafe);
+        buf[17] = self.failsafe_reason.map_or(0, |r| r.reason_code());
+        buf[18] = band_code(self.band);
+        buf[19] = if self.clean { FLAG_CLEAN } else { 0 };
+        buf[20..24].copy_from_slice(&self.yellow_used_ticks.to_le_bytes());
+        buf[24..28].copy_from_slice(&self.ops_in_window.to_le_bytes());
+        buf[28..32].copy_from_slice(&self.usage_in_window.inferences.to_le_bytes());
+        buf[32..36].copy_from_slice(&self.usage_in_window.spikes.to_le_bytes());
+        buf[36..40].copy_from_slice(&self.usage_in_window.energy_mj.to_le_bytes());
+        buf[40..48].copy_from_slice(&self.config_version.unwrap_or(0).to_le_bytes());
+        let crc = crc32(&buf[..CRC_OFFSET]);
+        buf[CRC_OFFSET..].copy_from_slice(&crc.to_le_bytes());
+        buf
+    }
+
+    /// `None` for erased, torn or foreign slots.
+    pub fn decode(buf: &[u8; CHECKPOINT_LEN]) -> Option<Self> {
+        if &buf[0..4] != CHECKPOINT_MAGIC {
+            return None;
+        }
+        if crc32(&buf[..CRC_OFFSET]) != u32_at(buf, CRC_OFFSET) {
+            return None;
+        }
+        let config_version = u64_at(buf, 40);
+        Some(Self {
+            sequence: u32_at(buf, 4),
+            tick: u64_at(buf, 8),
+            failsafe: mode_from_code(buf[16])?,
+            failsafe_reason: FailsafeReason::from_reason_code(buf[17]),
+            band: band_from_code(buf[18])?,
+            clean: buf[19] & FLAG_CLEAN != 0,
+            yellow_used_ticks: u32_at(buf, 20),
+            ops_in_window: u32_at(buf, 24),
+            usage_in_window: QuotaUsage {
+                inferences: u32_at(buf, 28),
+                spikes: u32_at(buf, 32),
+                energy_mj: u32_at(buf, 36),
+            },
+            config_version: (config_version != 0).then_some(config_version),
+        })
+    }
+
+    /// Failsafe mode to resume in. Observation-only and recovery resume in
+    /// observation-only with a fresh dwell, and a checkpoint left by an
+    /// unclean shutdown during a red event never resumes in `Normal`.
+    pub fn resume_mode(&self) -> (FailsafeMode, Option<FailsafeReason>) {
+        match self.failsafe {
+            _ if !self.clean && self.band.is_red() => {
+                (FailsafeMode::ObservationOnly, Some(FailsafeReason::RedBand))
+            }
+            FailsafeMode::ObservationOnly | FailsafeMode::Recovering => {
+                (FailsafeMode::ObservationOnly, self.failsafe_reason)
+            }
+            mode => (mode, self.failsafe_reason),
+        }
+    }
+}
+
+/// Where the next checkpoint goes and what it is numbered.
+#[derive(Copy, Clone, Debug, Default)]
+pub(crate) struct CheckpointCursor {
+    pub sequence: u32,
+    pub next_slot: u8,
+    pub last_tick: Option<u64>,
+    /// Band and failsafe mode of the last checkpoint written.
+    pub last_state: Option<(BandState, FailsafeMode)>,
+}
+
+impl CheckpointCursor {
+    /// Newest valid checkpoint in `store`, and the cursor that follows it.
+    pub fn scan<S: NonVolatileStore>(
+        store: &mut S,
+    ) -> Result<(Option<Checkpoint>, Self), S::Error> {
+        let mut newest: Option<(u8, Checkpoint)> = None;
+        let mut buf = [0u8; CHECKPOINT_LEN];
+        for slot in 0..store.slots() {
+            store.read_slot(slot, &mut buf)?;
+            if let Some(checkpoint) = Checkpoint::decode(&buf) {
+                if newest.is_none_or(|(_, n)| checkpoint.sequence > n.sequence) {
+                    newest = Some((slot, checkpoint));
+                }
+            }
+        }
+        let cursor = match newest {
+            Some((slot, checkpoint)) => Self {
+                sequence: checkpoint.sequence.wrapping_add(1),
+                next_slot: (slot + 1) % store.slots().max(1),
+                last_tick: None,
+                last_state: None,
+            },
+            None => Self::default(),
+        };
+        Ok((newest.map(|(_, c)| c), cursor))
+    }
+
+    pub fn write<S: NonVolatileStore>(
+        &mut self,
+        store: &mut S,
+        checkpoint: &Checkpoint,
+    ) -> Result<(), S::Error> {
+        store.write_slot(self.next_slot, &checkpoint.encode())?;
+        self.sequence = self.sequence.wrapping_add(1);
+        self.next_slot = (self.next_slot + 1) % store.slots().max(1);
+        self.last_tick = Some(checkpoint.tick);
+        self.last_state = Some((checkpoint.band, checkpoint.failsafe));
+        Ok(())
+    }
+}
+
+/// CRC-32 (IEEE 802.3, reflected).
+pub fn crc32(data: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for byte in data {
+        crc ^= *byte as u32;
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xEDB8_8320 & mask);
+        }
+    }
+    !crc
+}
+
+fn u32_at(buf: &[u8], at: usize) -> u32 {
+    let mut bytes = [0u8; 4];
+    bytes.copy_from_slice(&buf[at..at + 4]);
+    u32::from_le_bytes(bytes)
+}
+
+fn u64_at(buf: &[u8], at: usize) -> u64 {
+    let mut bytes = [0u8; 8];
+    bytes.copy_from_slice(&buf[at..at + 8]);
+    u64::from_le_bytes(bytes)
+}
+
+fn mode_code(mode: FailsafeMode) -> u8 {
+    match mode {
+        FailsafeMode::Normal => 0,
+        FailsafeMode::Degraded => 1,
+        FailsafeMode::ObservationOnly => 2,
+        FailsafeMode::Recovering => 3,
+    }
+}
+
+fn mode_from_code(code: u8) -> Option<FailsafeMode> {
+    match code {
+        0 => Some(FailsafeMode::Normal),
+        1 => Some(FailsafeMode::Degraded),
+        2 => Some(FailsafeMode::ObservationOnly),
+        3 => Some(FailsafeMode::Recovering),
+        _ => None,
+    }
+}
+
+fn band_code(band: BandState) -> u8 {
+    match band {
+        BandState::Green => 0,
+        BandState::Yellow => 1,
+        BandState::Red => 2,
+    }
+}
+
+fn band_from_code(code: u8) -> Option<BandState> {
+    match code {
+        0 => Some(BandState::Green),
+        1 => Some(BandState::Yellow),
+        2 => Some(BandState::Red),
+        _ => None,
+    }
+}
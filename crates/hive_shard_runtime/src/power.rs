@@ -0,0 +1,152 @@
+use heapless::Vec;
+use serde::{Deserialize, Serialize};
+
+use crate::actuator::{ActuatorCommandFrame, ActuatorKind};
+use crate::timebase::TickCounter;
+
+/// Battery and harvest reading from the charge controller.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct PowerState {
+    pub battery_soc_pct: u8,
+    /// Power currently harvested, e.g. by the solar panel.
+    pub harvest_mw: u32,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum PowerMode {
+    Normal,
+    /// Controller runs at a reduced rate.
+    Conserve,
+    /// Only sensing and logging; the charge left is held for the night.
+    Reserve,
+}
+
+/// Switches `actuator` off below `below_soc_pct`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct LoadShed {
+    pub actuator: ActuatorKind,
+    pub below_soc_pct: u8,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PowerPolicy {
+    pub battery_capacity_mwh: u32,
+    /// Draw of sensing, logging and uplink with the controller idle.
+    pub baseline_load_mw: u32,
+    /// Hours of baseline load held in reserve, covering the longest night.
+    pub night_reserve_hours: u16,
+    pub conserve_below_soc_pct: u8,
+    /// In conserve mode the controller runs once every this many periods.
+    pub conserve_inference_interval: u16,
+    /// Harvest that lifts conserve mode regardless of charge.
+    pub harvest_surplus_mw: u32,
+    /// Charge a mode must recover by before it is left.
+    pub hysteresis_pct: u8,
+    pub shed: Vec<LoadShed, 4>,
+}
+
+impl PowerPolicy {
+    /// Charge covering `night_reserve_hours` of baseline load, rounded up.
+    pub fn reserve_soc_pct(&self) -> u8 {
+        let reserve_mwh = self.baseline_load_mw as u64 * self.night_reserve_hours as u64;
+        let pct = (reserve_mwh * 100).div_ceil(self.battery_capacity_mwh.max(1) as u64);
+        pct.min(100) as u8
+    }
+}
+
+/// Tracks the latest power reading and the operating mode it allows.
+///
+/// Until a reading arrives the shard is assumed to be well supplied.
+/// Periods the controller is skipped are observation-only.
+#[derive(Clone, Debug)]
+pub struct PowerManager {
+    state: Option<PowerState>,
+    mode: PowerMode,
+}
+
+impl PowerManager {
+    pub fn new() -> Self {
+        Self {
+            state: None,
+            mode: PowerMode::Normal,
+        }
+    }
+
+    pub fn state(&self) -> Option<PowerState> {
+        self.state
+    }
+
+    pub fn mode(&self) -> PowerMode {
+        self.mode
+    }
+
+    pub fn update(&mut self, state: PowerState, policy: &PowerPolicy) {
+        let soc = state.battery_soc_pct;
+        let reserve = policy.reserve_soc_pct();
+        let conserve = policy.conserve_below_soc_pct.max(reserve);
+        // Leaving a lower mode needs the charge back above its threshold
+        // plus the hysteresis.
+        let margin = |threshold: u8, held: bool| {
+            if held {
+                threshold.saturating_add(policy.hysteresis_pct)
+            } else {
+                threshold
+            }
+        };
+
+        self.mode = if soc <= margin(reserve, self.mode == PowerMode::Reserve) {
+            PowerMode::Reserve
+        } else if state.harvest_mw < policy.harvest_surplus_mw
+            && soc < margin(conserve, self.mode != PowerMode::Normal)
+        {
+            PowerMode::Conserve
+        } else {
+            PowerMode::Normal
+        };
+        self.state = Some(state);
+    }
+
+    /// Re-evaluates the latest reading under a new policy, keeping the
+    /// current mode for hysteresis; no policy means no restrictions.
+    pub fn set_policy(&mut self, policy: Option<&PowerPolicy>) {
+        match (policy, self.state) {
+            (Some(policy), Some(state)) => self.update(state, policy),
+            (Some(_), None) => {}
+            (None, _) => self.mode = PowerMode::Normal,
+        }
+    }
+
+    /// Whether the controller may run at `tick`.
+    pub fn admits_inference(&self, tick: TickCounter, policy: &PowerPolicy) -> bool {
+        match self.mode {
+            PowerMode::Normal => true,
+            PowerMode::Conserve => tick
+                .ticks()
+                .is_multiple_of(policy.conserve_inference_interval.max(1) as u64),
+            PowerMode::Reserve => false,
+        }
+    }
+
+    /// Switches off actuators whose shed threshold is above the charge.
+    pub fn shed(&self, frame: &mut ActuatorCommandFrame, policy: &PowerPolicy) {
+        let Some(state) = self.state else {
+            return;
+        };
+        for channel in frame.channels.iter_mut() {
+            let kind = channel.command.kind();
+            if policy
+                .shed
+                .iter()
+                .any(|s| s.actuator == kind && state.battery_soc_pct < s.below_soc_pct)
+            {
+                channel.command.switch_off();
+            }
+        }
+    }
+}
+
+impl Default for PowerManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
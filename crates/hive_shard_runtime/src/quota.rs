@@ -0,0 +1,186 @@
+use serde::{Deserialize, Serialize};
+
+use crate::limits::{QuotaProfile, ShardLimits};
+use crate::timebase::{TickCounter, MS_PER_MINUTE};
+
+/// Number of buckets each sliding window is divided into.
+pub const QUOTA_WINDOW_BUCKETS: usize = 16;
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum QuotaViolation {
+    WindowOps,
+    InferenceRate,
+    SpikeBudget,
+    EnergyBudget,
+}
+
+impl QuotaViolation {
+    pub fn reason_code(&self) -> u8 {
+        match self {
+            QuotaViolation::WindowOps => 0x10,
+            QuotaViolation::InferenceRate => 0x11,
+            QuotaViolation::SpikeBudget => 0x12,
+            QuotaViolation::EnergyBudget => 0x13,
+        }
+    }
+
+    pub fn from_reason_code(code: u8) -> Option<Self> {
+        match code {
+            0x10 => Some(QuotaViolation::WindowOps),
+            0x11 => Some(QuotaViolation::InferenceRate),
+            0x12 => Some(QuotaViolation::SpikeBudget),
+            0x13 => Some(QuotaViolation::EnergyBudget),
+            _ => None,
+        }
+    }
+}
+
+/// Resources consumed by one controller step.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct QuotaUsage {
+    pub inferences: u32,
+    pub spikes: u32,
+    pub energy_mj: u32,
+}
+
+/// Bucketed sliding-window counter over tick indices.
+///
+/// The window is split into up to `N` equal slices, so the effective window
+/// may overshoot the configured length by at most one slice.
+#[derive(Clone, Debug)]
+pub struct SlidingWindow<const N: usize = QUOTA_WINDOW_BUCKETS> {
+    bucket_ticks: u64,
+    /// Slices making up the window; fewer than `N` for short windows.
+    span: u64,
+    epochs: [u64; N],
+    counts: [u32; N],
+}
+
+impl<const N: usize> SlidingWindow<N> {
+    pub fn new(window_ticks: u64) -> Self {
+        let window_ticks = window_ticks.max(1);
+        let bucket_ticks = window_ticks.div_ceil(N as u64);
+        Self {
+            bucket_ticks,
+            span: window_ticks.div_ceil(bucket_ticks).min(N as u64),
+            epochs: [u64::MAX; N],
+            counts: [0; N],
+        }
+    }
+
+    pub fn total(&self, now: u64) -> u32 {
+        let current = now / self.bucket_ticks;
+        let oldest = current.saturating_sub(self.span - 1);
+        self.epochs
+            .iter()
+            .zip(self.counts.iter())
+            .filter(|(epoch, _)| **epoch != u64::MAX && **epoch >= oldest && **epoch <= current)
+            .fold(0u32, |acc, (_, count)| acc.saturating_add(*count))
+    }
+
+    pub fn add(&mut self, now: u64, amount: u32) {
+        let epoch = now / self.bucket_ticks;
+        let slot = (epoch % N as u64) as usize;
+        if self.epochs[slot] != epoch {
+            self.epochs[slot] = epoch;
+            self.counts[slot] = 0;
+        }
+        self.counts[slot] = self.counts[slot].saturating_add(amount);
+    }
+}
+
+/// Runtime ledger debited against `ShardLimits` and `QuotaProfile`.
+#[derive(Clone, Debug)]
+pub struct QuotaLedger {
+    ops: SlidingWindow,
+    inferences_per_minute: SlidingWindow,
+    spikes: SlidingWindow,
+    energy_mj: SlidingWindow,
+}
+
+impl QuotaLedger {
+    pub fn new(quota: &QuotaProfile, clock: &TickCounter) -> Self {
+        let window_ticks = quota.window_ticks as u64;
+        let minute_ticks = clock.ticks_for_ms(MS_PER_MINUTE);
+        Self {
+            ops: SlidingWindow::new(window_ticks),
+            inferences_per_minute: SlidingWindow::new(minute_ticks),
+            spikes: SlidingWindow::new(window_ticks),
+            energy_mj: SlidingWindow::new(window_ticks),
+        }
+    }
+
+    pub fn ops_in_window(&self, now: u64) -> u32 {
+        self.ops.total(now)
+    }
+
+    pub fn inferences_in_last_minute(&self, now: u64) -> u32 {
+        self.inferences_per_minute.total(now)
+    }
+
+    pub fn spikes_in_window(&self, now: u64) -> u32 {
+        self.spikes.total(now)
+    }
+
+    pub fn energy_mj_in_window(&self, now: u64) -> u32 {
+        self.energy_mj.total(now)
+    }
+
+    pub fn remaining_ops(&self, now: u64, quota: &QuotaProfile) -> u32 {
+        quota.max_ops_in_window.saturating_sub(self.ops_in_window(now))
+    }
+
+    /// Charges usage carried over from a checkpoint at `now`, so it ages
+    /// out no sooner than it would have without the reboot.
+    pub(crate) fn restore(&mut self, now: u64, ops: u32, usage: &QuotaUsage) {
+        self.ops.add(now, ops);
+        self.inferences_per_minute.add(now, usage.inferences);
+        self.spikes.add(now, usage.spikes);
+        self.energy_mj.add(now, usage.energy_mj);
+    }
+
+    pub(crate) fn admit_inference(
+        &mut self,
+        now: u64,
+        limits: &ShardLimits,
+        quota: &QuotaProfile,
+    ) -> Result<(), QuotaViolation> {
+        if self.ops.total(now) >= quota.max_ops_in_window {
+            return Err(QuotaViolation::WindowOps);
+        }
+        if self.inferences_per_minute.total(now) >= limits.max_inferences_per_minute {
+            return Err(QuotaViolation::InferenceRate);
+        }
+        self.ops.add(now, 1);
+        self.inferences_per_minute.add(now, 1);
+        Ok(())
+    }
+
+    /// Charges a step's spikes and energy, then checks both the per-step
+    /// ceilings in `ShardLimits` and the window totals in `QuotaProfile`.
+    pub(crate) fn debit_usage(
+        &mut self,
+        now: u64,
+        usage: &QuotaUsage,
+        limits: &ShardLimits,
+        quota: &QuotaProfile,
+    ) -> Result<(), QuotaViolation> {
+        self.spikes.add(now, usage.spikes);
+        self.energy_mj.add(now, usage.energy_mj);
+
+        if usage.spikes > limits.max_spikes_per_period
+            || self.spikes.total(now) > quota.max_spikes_in_window
+        {
+            return Err(QuotaViolation::SpikeBudget);
+        }
+        let energy_ceiling = limits
+            .max_joules_per_inference_mj
+            .saturating_mul(usage.inferences.max(1));
+        if usage.energy_mj > energy_ceiling
+            || self.energy_mj.total(now) > quota.max_energy_mj_in_window
+        {
+            return Err(QuotaViolation::EnergyBudget);
+        }
+        Ok(())
+    }
+}
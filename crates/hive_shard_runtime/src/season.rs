@@ -0,0 +1,144 @@
+use heapless::Vec;
+use serde::{Deserialize, Serialize};
+
+use crate::band::{BandThresholds, BioloadThresholds};
+
+/// Upper bound on `SeasonalThresholds::profiles`.
+pub const MAX_SEASON_PROFILES: usize = 8;
+
+const DAYS_PER_YEAR: i32 = 365;
+/// Offset between the northern and southern seasonal calendars.
+const HEMISPHERE_SHIFT_DAYS: i32 = 182;
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum Hemisphere {
+    Northern,
+    Southern,
+}
+
+/// Thresholds that apply exactly on `day_of_year`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SeasonProfile {
+    /// Northern-hemisphere day of year (1..=365) this profile is centred on.
+    pub day_of_year: u16,
+    pub bands: BandThresholds,
+    pub bioload: BioloadThresholds,
+}
+
+/// Day-of-year threshold table.
+///
+/// Profiles are written against the northern calendar and shifted half a year
+/// for southern sites. Between two profiles every threshold is interpolated
+/// linearly by date, wrapping from the last profile of the year to the first;
+/// the debounce window is taken from the earlier profile.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SeasonalThresholds {
+    pub hemisphere: Hemisphere,
+    pub profiles: Vec<SeasonProfile, MAX_SEASON_PROFILES>,
+}
+
+impl SeasonalThresholds {
+    /// Thresholds for a UTC day of year, or `None` when no profiles are set.
+    pub fn resolve(&self, day_of_year: u16) -> Option<(BandThresholds, BioloadThresholds)> {
+        let day = self.calendar_day(day_of_year);
+        let days = |p: &SeasonProfile| (p.day_of_year as i32 - 1).rem_euclid(DAYS_PER_YEAR);
+
+        // Latest profile at or before `day`, wrapping to the year's last one.
+        let before = self
+            .profiles
+            .iter()
+            .filter(|p| days(p) <= day)
+            .max_by_key(|p| days(p))
+            .or_else(|| self.profiles.iter().max_by_key(|p| days(p)))?;
+        let after = self
+            .profiles
+            .iter()
+            .filter(|p| days(p) > day)
+            .min_by_key(|p| days(p))
+            .or_else(|| self.profiles.iter().min_by_key(|p| days(p)))?;
+
+        let span = (days(after) - days(before)).rem_euclid(DAYS_PER_YEAR);
+        if span == 0 {
+            return Some((before.bands.clone(), before.bioload.clone()));
+        }
+        let into = (day - days(before)).rem_euclid(DAYS_PER_YEAR);
+        let f = Fraction { num: into, den: span };
+        Some((
+            f.bands(&before.bands, &after.bands),
+            BioloadThresholds {
+                elevated_mites_per_100_bees: f.u8(
+                    before.bioload.elevated_mites_per_100_bees,
+                    after.bioload.elevated_mites_per_100_bees,
+                ),
+                critical_mites_per_100_bees: f.u8(
+                    before.bioload.critical_mites_per_100_bees,
+                    after.bioload.critical_mites_per_100_bees,
+                ),
+            },
+        ))
+    }
+
+    /// Zero-based northern-calendar day for a UTC day of year.
+    fn calendar_day(&self, day_of_year: u16) -> i32 {
+        let day = (day_of_year as i32 - 1).rem_euclid(DAYS_PER_YEAR);
+        match self.hemisphere {
+            Hemisphere::Northern => day,
+            Hemisphere::Southern => (day + HEMISPHERE_SHIFT_DAYS).rem_euclid(DAYS_PER_YEAR),
+        }
+    }
+}
+
+struct Fraction {
+    num: i32,
+    den: i32,
+}
+
+impl Fraction {
+    fn i16(&self, a: i16, b: i16) -> i16 {
+        (a as i32 + (b as i32 - a as i32) * self.num / self.den) as i16
+    }
+
+    fn u8(&self, a: u8, b: u8) -> u8 {
+        (a as i32 + (b as i32 - a as i32) * self.num / self.den) as u8
+    }
+
+    fn bands(&self, a: &BandThresholds, b: &BandThresholds) -> BandThresholds {
+        BandThresholds {
+            yellow_min_temp_c: self.i16(a.yellow_min_temp_c, b.yellow_min_temp_c),
+            yellow_max_temp_c: self.i16(a.yellow_max_temp_c, b.yellow_max_temp_c),
+            red_min_temp_c: self.i16(a.red_min_temp_c, b.red_min_temp_c),
+            red_max_temp_c: self.i16(a.red_max_temp_c, b.red_max_temp_c),
+            yellow_min_humidity_pct: self.u8(a.yellow_min_humidity_pct, b.yellow_min_humidity_pct),
+            yellow_max_humidity_pct: self.u8(a.yellow_max_humidity_pct, b.yellow_max_humidity_pct),
+            red_min_humidity_pct: self.u8(a.red_min_humidity_pct, b.red_min_humidity_pct),
+            red_max_humidity_pct: self.u8(a.red_max_humidity_pct, b.red_max_humidity_pct),
+            yellow_max_acoustic_surplus_db: self.i16(
+                a.yellow_max_acoustic_surplus_db,
+                b.yellow_max_acoustic_surplus_db,
+            ),
+            red_max_acoustic_surplus_db: self.i16(
+                a.red_max_acoustic_surplus_db,
+                b.red_max_acoustic_surplus_db,
+            ),
+            yellow_max_daily_mortality_pct: self.u8(
+                a.yellow_max_daily_mortality_pct,
+                b.yellow_max_daily_mortality_pct,
+            ),
+            red_max_daily_mortality_pct: self.u8(
+                a.red_max_daily_mortality_pct,
+                b.red_max_daily_mortality_pct,
+            ),
+            yellow_max_brood_gradient_c: self.i16(
+                a.yellow_max_brood_gradient_c,
+                b.yellow_max_brood_gradient_c,
+            ),
+            red_max_brood_gradient_c: self.i16(a.red_max_brood_gradient_c, b.red_max_brood_gradient_c),
+            hysteresis_temp_c: self.i16(a.hysteresis_temp_c, b.hysteresis_temp_c),
+            hysteresis_humidity_pct: self.u8(a.hysteresis_humidity_pct, b.hysteresis_humidity_pct),
+            hysteresis_acoustic_db: self.i16(a.hysteresis_acoustic_db, b.hysteresis_acoustic_db),
+            hysteresis_mortality_pct: self.u8(a.hysteresis_mortality_pct, b.hysteresis_mortality_pct),
+            debounce_n: a.debounce_n,
+            debounce_m: a.debounce_m,
+        }
+    }
+}
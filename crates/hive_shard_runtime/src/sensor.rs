@@ -1,5 +1,28 @@
+use bitflags::bitflags;
 use serde::{Deserialize, Serialize};
 
+bitflags! {
+    /// One bit per `SensorSnapshot` reading.
+    #[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+    pub struct SensorFields: u8 {
+        const BROOD_TEMP = 1 << 0;
+        const BROOD_HUMIDITY = 1 << 1;
+        const ACOUSTIC_SURPLUS = 1 << 2;
+        const DAILY_MORTALITY = 1 << 3;
+        const HIVE_WEIGHT = 1 << 4;
+        const FORAGER_RETURN = 1 << 5;
+        const VARROA = 1 << 6;
+        /// Readings that feed `BandState::evaluate`.
+        const BAND_INPUTS = Self::BROOD_TEMP.bits()
+            | Self::BROOD_HUMIDITY.bits()
+            | Self::ACOUSTIC_SURPLUS.bits()
+            | Self::DAILY_MORTALITY.bits();
+    }
+}
+
+/// Number of readings in a `SensorSnapshot`.
+pub const SENSOR_FIELD_COUNT: usize = 7;
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct SensorSnapshot {
     pub brood_temp_c: i16,
@@ -9,4 +32,37 @@ pub struct SensorSnapshot {
     pub hive_weight_kg_x10: i32,
     pub forager_return_delta_pct: i16,
     pub varroa_mites_per_100_bees: u8,
+    /// Largest brood temperature change per frame slot, set by probe fusion;
+    /// 0 for single-probe hives, which therefore never detect a cluster.
+    #[serde(default)]
+    pub brood_gradient_c: i16,
+    /// Readings the acquisition layer could not obtain this tick.
+    #[serde(default)]
+    pub missing: SensorFields,
+}
+
+impl SensorSnapshot {
+    /// Readings in `SensorFields` bit order, widened to `i32`.
+    pub fn values(&self) -> [i32; SENSOR_FIELD_COUNT] {
+        [
+            self.brood_temp_c as i32,
+            self.brood_humidity_pct as i32,
+            self.acoustic_surplus_db as i32,
+            self.daily_mortality_pct as i32,
+            self.hive_weight_kg_x10,
+            self.forager_return_delta_pct as i32,
+            self.varroa_mites_per_100_bees as i32,
+        ]
+    }
+
+    /// Inverse of `values`; each value is saturated into its field's range.
+    pub fn set_values(&mut self, values: &[i32; SENSOR_FIELD_COUNT]) {
+        self.brood_temp_c = values[0].clamp(i16::MIN as i32, i16::MAX as i32) as i16;
+        self.brood_humidity_pct = values[1].clamp(0, u8::MAX as i32) as u8;
+        self.acoustic_surplus_db = values[2].clamp(i16::MIN as i32, i16::MAX as i32) as i16;
+        self.daily_mortality_pct = values[3].clamp(0, u8::MAX as i32) as u8;
+        self.hive_weight_kg_x10 = values[4];
+        self.forager_return_delta_pct = values[5].clamp(i16::MIN as i32, i16::MAX as i32) as i16;
+        self.varroa_mites_per_100_bees = values[6].clamp(0, u8::MAX as i32) as u8;
+    }
 }
@@ -0,0 +1,156 @@
+use bitflags::bitflags;
+use serde::{Deserialize, Serialize};
+
+use crate::sensor::{SensorFields, SensorSnapshot, SENSOR_FIELD_COUNT};
+
+bitflags! {
+    #[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+    pub struct SensorHealth: u8 {
+        const OUT_OF_RANGE = 1 << 0;
+        const STUCK = 1 << 1;
+        const STEP_JUMP = 1 << 2;
+        const MISSING = 1 << 3;
+    }
+}
+
+/// Physical plausibility envelope for one reading.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct FieldPlausibility {
+    pub min: i32,
+    pub max: i32,
+    /// Largest believable change between consecutive samples; 0 disables.
+    pub max_step: i32,
+    /// Identical consecutive samples before a reading counts as stuck; 0 disables.
+    pub stuck_samples: u16,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SensorPlausibility {
+    pub brood_temp_c: FieldPlausibility,
+    pub brood_humidity_pct: FieldPlausibility,
+    pub acoustic_surplus_db: FieldPlausibility,
+    pub daily_mortality_pct: FieldPlausibility,
+    pub hive_weight_kg_x10: FieldPlausibility,
+    pub forager_return_delta_pct: FieldPlausibility,
+    pub varroa_mites_per_100_bees: FieldPlausibility,
+}
+
+impl SensorPlausibility {
+    fn fields(&self) -> [&FieldPlausibility; SENSOR_FIELD_COUNT] {
+        [
+            &self.brood_temp_c,
+            &self.brood_humidity_pct,
+            &self.acoustic_surplus_db,
+            &self.daily_mortality_pct,
+            &self.hive_weight_kg_x10,
+            &self.forager_return_delta_pct,
+            &self.varroa_mites_per_100_bees,
+        ]
+    }
+}
+
+/// Per-reading health for the most recent snapshot.
+#[derive(Clone, Debug, Default)]
+pub struct SensorHealthReport {
+    health: [SensorHealth; SENSOR_FIELD_COUNT],
+}
+
+impl SensorHealthReport {
+    pub fn get(&self, field: SensorFields) -> SensorHealth {
+        let index = field.bits().trailing_zeros() as usize;
+        self.health.get(index).copied().unwrap_or_default()
+    }
+
+    /// Readings with at least one health flag raised.
+    pub fn faulted(&self) -> SensorFields {
+        let mut out = SensorFields::empty();
+        for (index, health) in self.health.iter().enumerate() {
+            if !health.is_empty() {
+                out |= SensorFields::from_bits_truncate(1 << index);
+            }
+        }
+        out
+    }
+
+    pub fn is_healthy(&self) -> bool {
+        self.faulted().is_empty()
+    }
+}
+
+#[derive(Copy, Clone, Debug, Default)]
+struct FieldTrack {
+    last: Option<i32>,
+    last_good: Option<i32>,
+    repeats: u16,
+}
+
+/// Validates each snapshot before band evaluation.
+///
+/// Faulted readings are replaced by their last healthy value so that a broken
+/// probe freezes the affected reading instead of driving the band.
+#[derive(Clone, Debug, Default)]
+pub struct SensorValidator {
+    tracks: [FieldTrack; SENSOR_FIELD_COUNT],
+    report: SensorHealthReport,
+}
+
+impl SensorValidator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn report(&self) -> &SensorHealthReport {
+        &self.report
+    }
+
+    /// Checks `snapshot` and returns a copy with faulted readings held at
+    /// their last healthy value.
+    pub fn validate(
+        &mut self,
+        snapshot: &SensorSnapshot,
+        plausibility: &SensorPlausibility,
+    ) -> SensorSnapshot {
+        let values = snapshot.values();
+        let mut sanitized = values;
+
+        for (index, limits) in plausibility.fields().iter().enumerate() {
+            let field = SensorFields::from_bits_truncate(1 << index);
+            let track = &mut self.tracks[index];
+            let value = values[index];
+            let mut health = SensorHealth::empty();
+
+            if snapshot.missing.contains(field) {
+                health |= SensorHealth::MISSING;
+            } else {
+                if value < limits.min || value > limits.max {
+                    health |= SensorHealth::OUT_OF_RANGE;
+                }
+                if let Some(last) = track.last {
+                    if limits.max_step > 0 && value.abs_diff(last) > limits.max_step as u32 {
+                        health |= SensorHealth::STEP_JUMP;
+                    }
+                    track.repeats = if value == last {
+                        track.repeats.saturating_add(1)
+                    } else {
+                        0
+                    };
+                }
+                if limits.stuck_samples > 0 && track.repeats >= limits.stuck_samples {
+                    health |= SensorHealth::STUCK;
+                }
+                track.last = Some(value);
+            }
+
+            if health.is_empty() {
+                track.last_good = Some(value);
+            } else if let Some(good) = track.last_good {
+                sanitized[index] = good;
+            }
+            self.report.health[index] = health;
+        }
+
+        let mut out = snapshot.clone();
+        out.set_values(&sanitized);
+        out
+    }
+}
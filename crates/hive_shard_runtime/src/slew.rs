@@ -0,0 +1,211 @@
+use crate::actuator::{ActuatorCommand, ActuatorCommandFrame, ActuatorZone};
+use crate::limits::ShardLimits;
+use crate::sensor::SensorSnapshot;
+use crate::timebase::{TickCounter, MS_PER_HOUR};
+
+/// Number of slices the rolling hour is divided into.
+pub const SLEW_WINDOW_BUCKETS: usize = 12;
+
+const ZONES: usize = ActuatorZone::ALL.len();
+
+/// Bucketed rolling minimum/maximum over tick indices.
+#[derive(Clone, Debug)]
+pub struct MinMaxWindow {
+    bucket_ticks: u64,
+    span: u64,
+    epochs: [u64; SLEW_WINDOW_BUCKETS],
+    mins: [i16; SLEW_WINDOW_BUCKETS],
+    maxs: [i16; SLEW_WINDOW_BUCKETS],
+}
+
+impl MinMaxWindow {
+    pub fn new(window_ticks: u64) -> Self {
+        let window_ticks = window_ticks.max(1);
+        let bucket_ticks = window_ticks.div_ceil(SLEW_WINDOW_BUCKETS as u64);
+        Self {
+            bucket_ticks,
+            span: window_ticks
+                .div_ceil(bucket_ticks)
+                .min(SLEW_WINDOW_BUCKETS as u64),
+            epochs: [u64::MAX; SLEW_WINDOW_BUCKETS],
+            mins: [i16::MAX; SLEW_WINDOW_BUCKETS],
+            maxs: [i16::MIN; SLEW_WINDOW_BUCKETS],
+        }
+    }
+
+    pub fn record(&mut self, now: u64, value: i16) {
+        let epoch = now / self.bucket_ticks;
+        let slot = (epoch % SLEW_WINDOW_BUCKETS as u64) as usize;
+        if self.epochs[slot] != epoch {
+            self.epochs[slot] = epoch;
+            self.mins[slot] = value;
+            self.maxs[slot] = value;
+        } else {
+            self.mins[slot] = self.mins[slot].min(value);
+            self.maxs[slot] = self.maxs[slot].max(value);
+        }
+    }
+
+    /// Returns `(min, max)` over the window ending at `now`, if anything was recorded.
+    pub fn range(&self, now: u64) -> Option<(i16, i16)> {
+        let current = now / self.bucket_ticks;
+        let oldest = current.saturating_sub(self.span - 1);
+        let mut out: Option<(i16, i16)> = None;
+        for slot in 0..SLEW_WINDOW_BUCKETS {
+            let epoch = self.epochs[slot];
+            if epoch == u64::MAX || epoch < oldest || epoch > current {
+                continue;
+            }
+            out = Some(match out {
+                Some((lo, hi)) => (lo.min(self.mins[slot]), hi.max(self.maxs[slot])),
+                None => (self.mins[slot], self.maxs[slot]),
+            });
+        }
+        out
+    }
+}
+
+/// Per-hour slew limiter applied to every frame leaving the runtime.
+///
+/// The heater setpoint may not move more than `max_delta_t_c_per_hour` and
+/// the fan duty not more than `max_fan_delta_pct_per_hour` across any
+/// rolling hour, and neither may push further once the measured brood
+/// temperature or acoustic surplus has already moved by the configured
+/// per-hour delta. Switching an actuator off is always allowed. Setpoint
+/// history is kept per zone; the measured deltas are hive-wide.
+#[derive(Clone, Debug)]
+pub struct SlewLimiter {
+    heater_setpoints: [MinMaxWindow; ZONES],
+    fan_setpoints: [MinMaxWindow; ZONES],
+    brood_temps: MinMaxWindow,
+    acoustic_db: MinMaxWindow,
+    last_heater_celsius: [i16; ZONES],
+    last_fan_duty_pct: [u8; ZONES],
+}
+
+impl SlewLimiter {
+    pub fn new(clock: &TickCounter) -> Self {
+        let hour_ticks = clock.ticks_for_ms(MS_PER_HOUR);
+        Self {
+            heater_setpoints: core::array::from_fn(|_| MinMaxWindow::new(hour_ticks)),
+            fan_setpoints: core::array::from_fn(|_| MinMaxWindow::new(hour_ticks)),
+            brood_temps: MinMaxWindow::new(hour_ticks),
+            acoustic_db: MinMaxWindow::new(hour_ticks),
+            last_heater_celsius: [0; ZONES],
+            last_fan_duty_pct: [0; ZONES],
+        }
+    }
+
+    pub fn observe(&mut self, tick: TickCounter, sensors: &SensorSnapshot) {
+        let now = tick.ticks();
+        self.brood_temps.record(now, sensors.brood_temp_c);
+        self.acoustic_db.record(now, sensors.acoustic_surplus_db);
+    }
+
+    pub fn limit(
+        &mut self,
+        frame: &mut ActuatorCommandFrame,
+        tick: TickCounter,
+        sensors: &SensorSnapshot,
+        limits: &ShardLimits,
+    ) {
+        let now = tick.ticks();
+        for setpoints in self.fan_setpoints.iter_mut() {
+            if setpoints.range(now).is_none() {
+                setpoints.record(now, 0);
+            }
+        }
+        let mut heater = [0i16; ZONES];
+        let mut fan = [0u8; ZONES];
+        for channel in frame.channels.iter_mut() {
+            let zone = channel.zone.index();
+            match &mut channel.command {
+                ActuatorCommand::Heater { celsius } => {
+                    *celsius = self.limit_heater(zone, *celsius, now, sensors, limits);
+                    heater[zone] = *celsius;
+                }
+                ActuatorCommand::Fan { duty_pct } => {
+                    *duty_pct = self.limit_fan(zone, *duty_pct, now, limits);
+                    fan[zone] = *duty_pct;
+                }
+                _ => {}
+            }
+        }
+
+        for (zone, celsius) in heater.iter().enumerate() {
+            if *celsius != 0 {
+                self.heater_setpoints[zone].record(now, *celsius);
+            }
+        }
+        for (zone, duty_pct) in fan.iter().enumerate() {
+            self.fan_setpoints[zone].record(now, *duty_pct as i16);
+        }
+        self.last_heater_celsius = heater;
+        self.last_fan_duty_pct = fan;
+    }
+
+    fn limit_heater(
+        &self,
+        zone: usize,
+        requested: i16,
+        now: u64,
+        sensors: &SensorSnapshot,
+        limits: &ShardLimits,
+    ) -> i16 {
+        if requested == 0 {
+            return 0;
+        }
+        let delta = limits.max_delta_t_c_per_hour.max(0);
+
+        // Anchor on the setpoints commanded this hour, or on the measured
+        // brood temperature when the heater has been idle.
+        let (mut low, mut high) = match self.heater_setpoints[zone].range(now) {
+            Some((lo, hi)) => (hi.saturating_sub(delta), lo.saturating_add(delta)),
+            None => (
+                sensors.brood_temp_c.saturating_sub(delta),
+                sensors.brood_temp_c.saturating_add(delta),
+            ),
+        };
+
+        if let Some((lo, hi)) = self.brood_temps.range(now) {
+            let hold = if self.last_heater_celsius[zone] != 0 {
+                self.last_heater_celsius[zone]
+            } else {
+                sensors.brood_temp_c
+            };
+            if sensors.brood_temp_c.saturating_sub(lo) >= delta {
+                high = high.min(hold);
+            }
+            if hi.saturating_sub(sensors.brood_temp_c) >= delta {
+                low = low.max(hold);
+            }
+        }
+
+        if low > high {
+            low = high;
+        }
+        requested.clamp(low, high)
+    }
+
+    fn limit_fan(&self, zone: usize, requested: u8, now: u64, limits: &ShardLimits) -> u8 {
+        if requested == 0 {
+            return 0;
+        }
+        let step = limits.max_fan_delta_pct_per_hour as i16;
+
+        // Anchor on the duties commanded this hour, off included, so an
+        // idle fan ramps up from zero.
+        let (lo, hi) = self.fan_setpoints[zone].range(now).unwrap_or((0, 0));
+        let (low, mut high) = (hi.saturating_sub(step), lo.saturating_add(step));
+
+        let delta = limits.max_delta_db_per_hour.max(1);
+        if let Some((lo, hi)) = self.acoustic_db.range(now) {
+            if hi.saturating_sub(lo) >= delta {
+                high = high.min(self.last_fan_duty_pct[zone] as i16);
+            }
+        }
+
+        let high = high.clamp(0, 100);
+        (requested as i16).clamp(low.clamp(0, high), high) as u8
+    }
+}
@@ -0,0 +1,36 @@
+use heapless::Vec;
+use serde::{Deserialize, Serialize};
+
+use crate::band::{BandState, BioloadState};
+use crate::colony::ColonyPhase;
+use crate::controller::StepUsage;
+use crate::duty::{DutyBudget, DUTY_REPORT_LEN};
+use crate::failsafe::{FailsafeMode, FailsafeReason};
+use crate::homing::HomingState;
+use crate::power::PowerMode;
+
+/// Point-in-time view of the runtime, suitable for uplink or logging.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ShardTelemetry {
+    pub tick: u64,
+    pub band: BandState,
+    pub bioload: BioloadState,
+    pub failsafe: FailsafeMode,
+    pub failsafe_reason: Option<FailsafeReason>,
+    pub last_step: StepUsage,
+    pub ops_in_window: u32,
+    pub inferences_in_last_minute: u32,
+    pub spikes_in_window: u32,
+    pub energy_mj_in_window: u32,
+    pub yellow_budget_remaining_secs: u32,
+    pub yellow_budget_exhausted: bool,
+    pub deadline_overruns: u32,
+    /// Actuators that have been on during the duty-cycle window.
+    pub duty_cycle: Vec<DutyBudget, DUTY_REPORT_LEN>,
+    pub weight_net_kg_x10_per_day: Option<i32>,
+    pub nectar_flow: bool,
+    pub homing: HomingState,
+    pub colony_phase: ColonyPhase,
+    pub power_mode: PowerMode,
+    pub battery_soc_pct: Option<u8>,
+}
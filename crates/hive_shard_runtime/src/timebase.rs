@@ -1,11 +1,119 @@
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+
+pub const MS_PER_SECOND: u64 = 1_000;
+pub const MS_PER_MINUTE: u64 = 60 * MS_PER_SECOND;
+pub const MS_PER_HOUR: u64 = 60 * MS_PER_MINUTE;
+pub const MS_PER_DAY: u64 = 24 * MS_PER_HOUR;
+
+/// Control period used when none is configured.
+pub const DEFAULT_TICK_PERIOD_MS: u32 = 1_000;
+
+/// Ties a tick index to a UTC wall-clock instant, e.g. after a GNSS or
+/// gateway time sync.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct UtcAnchor {
+    pub tick: u64,
+    pub unix_ms: i64,
+}
+
+/// Monotonic control-loop clock.
+///
+/// Counts control periods since boot; all arithmetic saturates so a
+/// long-running shard never wraps into the past.
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
-pub enum FailsafeMode {
-    Normal,
-    ObservationOnly,
+pub struct TickCounter {
+    ticks: u64,
+    period_ms: u32,
+    anchor: Option<UtcAnchor>,
+}
+
+impl TickCounter {
+    pub fn new() -> Self {
+        Self::with_period_ms(DEFAULT_TICK_PERIOD_MS)
+    }
+
+    pub fn with_period_ms(period_ms: u32) -> Self {
+        Self {
+            ticks: 0,
+            period_ms: period_ms.max(1),
+            anchor: None,
+        }
+    }
+
+    pub fn increment(&mut self) {
+        self.ticks = self.ticks.saturating_add(1);
+    }
+
+    pub fn ticks(&self) -> u64 {
+        self.ticks
+    }
+
+    pub fn period_ms(&self) -> u32 {
+        self.period_ms
+    }
+
+    /// Milliseconds since boot.
+    pub fn monotonic_ms(&self) -> u64 {
+        self.ticks_to_ms(self.ticks)
+    }
+
+    pub fn ticks_to_ms(&self, ticks: u64) -> u64 {
+        ticks.saturating_mul(self.period_ms as u64)
+    }
+
+    /// Smallest number of ticks covering at least `ms`.
+    pub fn ticks_for_ms(&self, ms: u64) -> u64 {
+        ms.div_ceil(self.period_ms as u64)
+    }
+
+    /// Ticks elapsed since `earlier`, zero if `earlier` lies in the future.
+    pub fn ticks_since(&self, earlier: u64) -> u64 {
+        self.ticks.saturating_sub(earlier)
+    }
+
+    pub fn ms_since(&self, earlier: u64) -> u64 {
+        self.ticks_to_ms(self.ticks_since(earlier))
+    }
+
+    /// Anchors the current tick to `unix_ms`; later anchors replace earlier ones.
+    pub fn anchor_utc(&mut self, unix_ms: i64) {
+        self.anchor = Some(UtcAnchor {
+            tick: self.ticks,
+            unix_ms,
+        });
+    }
+
+    pub fn utc_anchor(&self) -> Option<UtcAnchor> {
+        self.anchor
+    }
+
+    /// Current UTC time in Unix milliseconds, if anchored.
+    pub fn utc_ms(&self) -> Option<i64> {
+        let anchor = self.anchor?;
+        let elapsed = self.ms_since(anchor.tick).min(i64::MAX as u64) as i64;
+        Some(anchor.unix_ms.saturating_add(elapsed))
+    }
+
+    pub fn utc_datetime(&self) -> Option<OffsetDateTime> {
+        let nanos = self.utc_ms()? as i128 * 1_000_000;
+        OffsetDateTime::from_unix_timestamp_nanos(nanos).ok()
+    }
+
+    /// Day of year (1..=366) in UTC, if anchored.
+    pub fn utc_day_of_year(&self) -> Option<u16> {
+        self.utc_datetime().map(|dt| dt.ordinal())
+    }
+
+    /// Seconds since UTC midnight, if anchored.
+    pub fn utc_seconds_of_day(&self) -> Option<u32> {
+        let ms = self.utc_ms()?.rem_euclid(MS_PER_DAY as i64);
+        Some((ms / MS_PER_SECOND as i64) as u32)
+    }
 }
 
-impl FailsafeMode {
-    pub fn is_observation_only(&self) -> bool {
-        matches!(self, FailsafeMode::ObservationOnly)
+impl Default for TickCounter {
+    fn default() -> Self {
+        Self::new()
     }
 }
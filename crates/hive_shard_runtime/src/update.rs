@@ -0,0 +1,103 @@
+use ed25519_dalek::{Signature, VerifyingKey};
+use serde::{Deserialize, Serialize};
+
+use crate::config::ShardConfig;
+
+/// Payload of a config update: JSON, signed as-is by the operator key.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SignedConfig {
+    /// Must exceed the installed version; updates are never rolled back.
+    pub version: u64,
+    pub config: ShardConfig,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum ConfigUpdateError {
+    NotProvisioned,
+    BadKey,
+    BadSignature,
+    Malformed,
+    Rollback { installed: u64, offered: u64 },
+    /// The update resizes runtime windows (tick period, quota or weight
+    /// windows), which only takes effect through a restart.
+    GeometryChanged,
+}
+
+/// Checks config updates against the provisioned operator key.
+#[derive(Clone, Debug)]
+pub struct ConfigVerifier {
+    key: VerifyingKey,
+    installed_version: u64,
+    /// Version applied before a reboot brought back an older built-in
+    /// config; it may be applied again, nothing older may.
+    applied_before_reboot: Option<u64>,
+}
+
+impl ConfigVerifier {
+    pub fn new(public_key: &[u8; 32], installed_version: u64) -> Result<Self, ConfigUpdateError> {
+        let key = VerifyingKey::from_bytes(public_key).map_err(|_| ConfigUpdateError::BadKey)?;
+        Ok(Self {
+            key,
+            installed_version,
+            applied_before_reboot: None,
+        })
+    }
+
+    /// Carries over the version recorded in the last checkpoint, so a
+    /// reboot onto an older built-in config does not reopen the versions
+    /// in between.
+    pub fn after_reboot(mut self, checkpointed_version: Option<u64>) -> Self {
+        self.applied_before_reboot = checkpointed_version.filter(|v| *v > self.installed_version);
+        self
+    }
+
+    pub fn installed_version(&self) -> u64 {
+        self.installed_version
+    }
+
+    /// Newest version applied, whether running or awaiting re-application
+    /// after a reboot.
+    pub fn newest_version(&self) -> u64 {
+        self.applied_before_reboot.unwrap_or(self.installed_version)
+    }
+
+    /// Verifies the signature over `payload` before parsing it, then rejects
+    /// any version not newer than the installed one. After a reboot the
+    /// version applied before it may be sent again.
+    pub fn verify(
+        &self,
+        payload: &[u8],
+        signature: &[u8; 64],
+    ) -> Result<SignedConfig, ConfigUpdateError> {
+        let signature = Signature::from_bytes(signature);
+        self.key
+            .verify_strict(payload, &signature)
+            .map_err(|_| ConfigUpdateError::BadSignature)?;
+        let (update, _) = serde_json_core::from_slice::<SignedConfig>(payload)
+            .map_err(|_| ConfigUpdateError::Malformed)?;
+        let accepted = match self.applied_before_reboot {
+            Some(applied) => update.version >= applied,
+            None => update.version > self.installed_version,
+        };
+        if !accepted {
+            return Err(ConfigUpdateError::Rollback {
+                installed: self.newest_version(),
+                offered: update.version,
+            });
+        }
+        Ok(update)
+    }
+
+    pub(crate) fn commit(&mut self, version: u64) {
+        self.installed_version = version;
+        self.applied_before_reboot = None;
+    }
+}
+
+/// Whether `next` keeps every setting that sizes a runtime window.
+pub(crate) fn same_geometry(current: &ShardConfig, next: &ShardConfig) -> bool {
+    current.tick_period_ms == next.tick_period_ms
+        && current.quota_profile.window_ticks == next.quota_profile.window_ticks
+        && current.weight_trend.sudden_drop_window_min == next.weight_trend.sudden_drop_window_min
+        && current.duty_cycle.window_min == next.duty_cycle.window_min
+}
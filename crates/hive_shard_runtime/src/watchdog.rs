@@ -0,0 +1,96 @@
+use core::cell::Cell;
+
+use serde::{Deserialize, Serialize};
+
+/// Microsecond clock used to time controller steps.
+pub trait MonotonicClock {
+    fn now_us(&self) -> u64;
+}
+
+/// Manually advanced clock for host-side tests and trace replay.
+#[derive(Debug, Default)]
+pub struct FakeClock {
+    now_us: Cell<u64>,
+}
+
+impl FakeClock {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn advance_us(&self, us: u64) {
+        self.now_us.set(self.now_us.get().saturating_add(us));
+    }
+
+    pub fn set_us(&self, us: u64) {
+        self.now_us.set(us);
+    }
+}
+
+impl MonotonicClock for FakeClock {
+    fn now_us(&self) -> u64 {
+        self.now_us.get()
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct WatchdogPolicy {
+    pub step_deadline_us: u32,
+    /// Consecutive overruns tolerated before falling back to observation-only.
+    pub max_consecutive_overruns: u16,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum DeadlineOutcome {
+    OnTime,
+    Overrun,
+    /// Overrun that exhausted `max_consecutive_overruns`.
+    Tripped,
+}
+
+/// Counts controller steps that missed their deadline.
+///
+/// A step cannot be preempted on a single-threaded shard, so a controller
+/// that never returns must still be caught by the hardware watchdog; this
+/// covers the slow-but-returning case. Late outputs are still applied until
+/// `max_consecutive_overruns` is reached, when the runtime falls back to
+/// observation-only.
+#[derive(Clone, Debug, Default)]
+pub struct DeadlineWatchdog {
+    consecutive_overruns: u16,
+    total_overruns: u32,
+    last_elapsed_us: u32,
+}
+
+impl DeadlineWatchdog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record(&mut self, elapsed_us: u64, policy: &WatchdogPolicy) -> DeadlineOutcome {
+        self.last_elapsed_us = elapsed_us.min(u32::MAX as u64) as u32;
+        if elapsed_us <= policy.step_deadline_us as u64 {
+            self.consecutive_overruns = 0;
+            return DeadlineOutcome::OnTime;
+        }
+        self.consecutive_overruns = self.consecutive_overruns.saturating_add(1);
+        self.total_overruns = self.total_overruns.saturating_add(1);
+        if self.consecutive_overruns >= policy.max_consecutive_overruns {
+            DeadlineOutcome::Tripped
+        } else {
+            DeadlineOutcome::Overrun
+        }
+    }
+
+    pub fn consecutive_overruns(&self) -> u16 {
+        self.consecutive_overruns
+    }
+
+    pub fn total_overruns(&self) -> u32 {
+        self.total_overruns
+    }
+
+    pub fn last_elapsed_us(&self) -> u32 {
+        self.last_elapsed_us
+    }
+}
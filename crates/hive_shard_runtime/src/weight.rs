@@ -0,0 +1,220 @@
+use heapless::Deque;
+use serde::{Deserialize, Serialize};
+
+use crate::band::{BandState, BioloadState};
+use crate::sensor::SensorSnapshot;
+use crate::slew::MinMaxWindow;
+use crate::timebase::{TickCounter, MS_PER_HOUR, MS_PER_MINUTE};
+
+/// Hourly slots kept for the day-over-day comparison.
+pub const WEIGHT_HISTORY_HOURS: usize = 48;
+/// Weight events buffered between calls to `pop_event`.
+pub const WEIGHT_EVENT_QUEUE: usize = 8;
+
+const HOURS_PER_DAY: usize = 24;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct WeightTrendConfig {
+    /// Boxes, frames and bees with no stores; the floor for depletion projection.
+    pub empty_hive_kg_x10: i32,
+    pub flow_onset_gain_kg_x10_per_day: i32,
+    /// Loss from the recent peak that counts as robbing or swarm departure.
+    pub sudden_drop_kg_x10: i32,
+    pub sudden_drop_window_min: u16,
+    /// How long a sudden drop holds the band at yellow or above.
+    pub drop_hold_min: u16,
+    pub starvation_warning_days: u16,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum WeightEventKind {
+    NectarFlowOnset { gain_kg_x10_per_day: i32 },
+    NectarFlowEnd,
+    SuddenDrop { drop_kg_x10: i32 },
+    StarvationRisk { days_left: u16 },
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct WeightEvent {
+    pub tick: u64,
+    pub kind: WeightEventKind,
+}
+
+/// Interprets the hive weight series.
+///
+/// Foragers leaving in the morning and returning at dusk swing the weight by
+/// a kilogram or more every day, so net gain is taken as the difference of
+/// consecutive 24-hour means, which cancels the daily cycle.
+#[derive(Clone, Debug)]
+pub struct WeightTrendAnalyzer {
+    hour_ticks: u64,
+    hour_epoch: u64,
+    hour_sum: i64,
+    hour_samples: u32,
+    /// Mean per clock hour, oldest first; `None` for hours without samples.
+    hourly: Deque<Option<i32>, WEIGHT_HISTORY_HOURS>,
+    recent: MinMaxWindow,
+    drop_until: Option<u64>,
+    in_flow: bool,
+    starvation_days: Option<u16>,
+    events: Deque<WeightEvent, WEIGHT_EVENT_QUEUE>,
+}
+
+impl WeightTrendAnalyzer {
+    pub fn new(config: &WeightTrendConfig, clock: &TickCounter) -> Self {
+        let drop_window_ms = config.sudden_drop_window_min as u64 * MS_PER_MINUTE;
+        Self {
+            hour_ticks: clock.ticks_for_ms(MS_PER_HOUR),
+            hour_epoch: 0,
+            hour_sum: 0,
+            hour_samples: 0,
+            hourly: Deque::new(),
+            recent: MinMaxWindow::new(clock.ticks_for_ms(drop_window_ms)),
+            drop_until: None,
+            in_flow: false,
+            starvation_days: None,
+            events: Deque::new(),
+        }
+    }
+
+    /// Feeds one weight reading. Callers skip ticks where the weight is
+    /// missing or faulted.
+    pub fn observe(
+        &mut self,
+        tick: TickCounter,
+        sensors: &SensorSnapshot,
+        config: &WeightTrendConfig,
+    ) {
+        let now = tick.ticks();
+        let weight = sensors.hive_weight_kg_x10;
+
+        let epoch = now / self.hour_ticks;
+        if self.hour_samples > 0 && epoch != self.hour_epoch {
+            let mean = (self.hour_sum / self.hour_samples as i64) as i32;
+            self.push_hour(Some(mean));
+            // Hours without a reading keep their slot so a day stays 24
+            // clock hours long.
+            let empty = (epoch - self.hour_epoch - 1).min(WEIGHT_HISTORY_HOURS as u64);
+            for _ in 0..empty {
+                self.push_hour(None);
+            }
+            self.hour_sum = 0;
+            self.hour_samples = 0;
+            self.evaluate_day(now, config);
+        }
+        self.hour_epoch = epoch;
+        self.hour_sum += weight as i64;
+        self.hour_samples += 1;
+
+        let clamped = weight.clamp(i16::MIN as i32, i16::MAX as i32) as i16;
+        self.recent.record(now, clamped);
+        if let Some((_, peak)) = self.recent.range(now) {
+            let drop = peak as i32 - weight;
+            if drop >= config.sudden_drop_kg_x10 && !self.drop_held(now) {
+                let hold = tick.ticks_for_ms(config.drop_hold_min as u64 * MS_PER_MINUTE);
+                self.drop_until = Some(now.saturating_add(hold));
+                self.push_event(now, WeightEventKind::SuddenDrop { drop_kg_x10: drop });
+            }
+        }
+    }
+
+    /// Net change between the last two 24-hour means, once 48 hours are in
+    /// and neither day is without readings.
+    pub fn net_kg_x10_per_day(&self) -> Option<i32> {
+        if self.hourly.len() < 2 * HOURS_PER_DAY {
+            return None;
+        }
+        Some(self.day_mean(0)? - self.day_mean(1)?)
+    }
+
+    pub fn in_nectar_flow(&self) -> bool {
+        self.in_flow
+    }
+
+    /// Projected days of stores left, while below `starvation_warning_days`.
+    pub fn starvation_days(&self) -> Option<u16> {
+        self.starvation_days
+    }
+
+    pub fn band_floor(&self, tick: TickCounter) -> BandState {
+        if self.starvation_days.is_some() || self.drop_held(tick.ticks()) {
+            BandState::Yellow
+        } else {
+            BandState::Green
+        }
+    }
+
+    pub fn bioload_floor(&self) -> BioloadState {
+        if self.starvation_days.is_some() {
+            BioloadState::Elevated
+        } else {
+            BioloadState::Nominal
+        }
+    }
+
+    /// Oldest unread weight event, if any.
+    pub fn pop_event(&mut self) -> Option<WeightEvent> {
+        self.events.pop_front()
+    }
+
+    fn evaluate_day(&mut self, now: u64, config: &WeightTrendConfig) {
+        let Some(net) = self.net_kg_x10_per_day() else {
+            return;
+        };
+        let today = self.day_mean(0).unwrap_or_default();
+
+        let onset = config.flow_onset_gain_kg_x10_per_day;
+        if !self.in_flow && net >= onset {
+            self.in_flow = true;
+            self.push_event(now, WeightEventKind::NectarFlowOnset {
+                gain_kg_x10_per_day: net,
+            });
+        } else if self.in_flow && net < onset / 2 {
+            self.in_flow = false;
+            self.push_event(now, WeightEventKind::NectarFlowEnd);
+        }
+
+        let stores = (today - config.empty_hive_kg_x10).max(0);
+        let days_left = if net < 0 {
+            Some((stores / -net).min(u16::MAX as i32) as u16)
+        } else {
+            None
+        };
+        let at_risk = days_left.filter(|d| *d < config.starvation_warning_days);
+        if let (None, Some(days_left)) = (self.starvation_days, at_risk) {
+            self.push_event(now, WeightEventKind::StarvationRisk { days_left });
+        }
+        self.starvation_days = at_risk;
+    }
+
+    /// Mean of the sampled hours in the `days_ago`-th most recent day.
+    fn day_mean(&self, days_ago: usize) -> Option<i32> {
+        let skip = self.hourly.len() - (days_ago + 1) * HOURS_PER_DAY;
+        let (sum, hours) = self
+            .hourly
+            .iter()
+            .skip(skip)
+            .take(HOURS_PER_DAY)
+            .flatten()
+            .fold((0i64, 0i64), |(sum, n), w| (sum + *w as i64, n + 1));
+        (hours > 0).then(|| (sum / hours) as i32)
+    }
+
+    fn push_hour(&mut self, mean: Option<i32>) {
+        if self.hourly.is_full() {
+            self.hourly.pop_front();
+        }
+        let _ = self.hourly.push_back(mean);
+    }
+
+    fn drop_held(&self, now: u64) -> bool {
+        self.drop_until.is_some_and(|until| now < until)
+    }
+
+    fn push_event(&mut self, tick: u64, kind: WeightEventKind) {
+        if self.events.is_full() {
+            self.events.pop_front();
+        }
+        let _ = self.events.push_back(WeightEvent { tick, kind });
+    }
+}
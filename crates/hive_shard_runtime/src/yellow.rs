@@ -0,0 +1,85 @@
+use serde::{Deserialize, Serialize};
+
+use crate::band::BandState;
+use crate::quota::SlidingWindow;
+use crate::timebase::{TickCounter, MS_PER_HOUR, MS_PER_SECOND};
+
+/// Hourly buckets in the rolling yellow-band histogram.
+pub const YELLOW_WINDOW_HOURS: usize = 72;
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum YellowBudgetAction {
+    ObservationOnly,
+    Notify,
+}
+
+/// Device-side mirror of the bundle's `TemporalEnvelope`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct YellowBudget {
+    pub max_hours_in_yellow_per_72h: u16,
+    pub on_exhausted: YellowBudgetAction,
+}
+
+/// Rolling 72-hour occupancy histogram of `BandState::Yellow`.
+///
+/// Red ticks are not charged; red trips the failsafe on its own.
+#[derive(Clone, Debug)]
+pub struct YellowBudgetTracker {
+    occupancy: SlidingWindow<YELLOW_WINDOW_HOURS>,
+    budget_ticks: u32,
+    exhausted: bool,
+}
+
+impl YellowBudgetTracker {
+    pub fn new(budget: &YellowBudget, clock: &TickCounter) -> Self {
+        let hour_ticks = clock.ticks_for_ms(MS_PER_HOUR);
+        Self {
+            occupancy: SlidingWindow::new(hour_ticks * YELLOW_WINDOW_HOURS as u64),
+            budget_ticks: budget_ticks(budget, clock),
+            exhausted: false,
+        }
+    }
+
+    /// Changes the budget, keeping the occupancy already recorded.
+    pub fn set_budget(&mut self, budget: &YellowBudget, clock: &TickCounter) {
+        self.budget_ticks = budget_ticks(budget, clock);
+    }
+
+    /// Charges a yellow sample; returns `true` on the sample the budget
+    /// runs out.
+    pub fn record(&mut self, tick: TickCounter, band: BandState) -> bool {
+        if band == BandState::Yellow {
+            self.occupancy.add(tick.ticks(), 1);
+        }
+        let was_exhausted = self.exhausted;
+        self.exhausted = self.is_exhausted(tick);
+        self.exhausted && !was_exhausted
+    }
+
+    /// Charges yellow time carried over from a checkpoint at `tick`.
+    pub(crate) fn restore(&mut self, tick: TickCounter, used_ticks: u32) {
+        self.occupancy.add(tick.ticks(), used_ticks);
+    }
+
+    pub fn used_ticks(&self, tick: TickCounter) -> u32 {
+        self.occupancy.total(tick.ticks())
+    }
+
+    pub fn remaining_ticks(&self, tick: TickCounter) -> u32 {
+        self.budget_ticks.saturating_sub(self.used_ticks(tick))
+    }
+
+    pub fn remaining_secs(&self, tick: TickCounter) -> u32 {
+        let ms = tick.ticks_to_ms(self.remaining_ticks(tick) as u64);
+        (ms / MS_PER_SECOND).min(u32::MAX as u64) as u32
+    }
+
+    pub fn is_exhausted(&self, tick: TickCounter) -> bool {
+        self.remaining_ticks(tick) == 0
+    }
+}
+
+fn budget_ticks(budget: &YellowBudget, clock: &TickCounter) -> u32 {
+    let budget_ms = budget.max_hours_in_yellow_per_72h as u64 * MS_PER_HOUR;
+    (budget_ms / clock.period_ms() as u64).min(u32::MAX as u64) as u32
+}
@@ -0,0 +1,109 @@
+use hive_shard_runtime::acoustic::{
+    AcousticAnalyzer, AcousticConfig, AcousticSignature, ACOUSTIC_BANDS_HZ, ACOUSTIC_BAND_COUNT,
+};
+
+const FS: u32 = 8_000;
+/// Twenty-hertz Goertzel resolution.
+const N: usize = 400;
+
+fn config() -> AcousticConfig {
+    AcousticConfig {
+        sample_rate_hz: FS,
+        baseline_acoustic_db: 30,
+        mic_offset_db: 0,
+        buzz_margin_db: 10,
+        piping_tonal_margin_db: 10,
+        roar_margin_db: 10,
+        sustain_frames: 3,
+    }
+}
+
+fn tones(hz: &[u16], amplitude: f64) -> Vec<i16> {
+    (0..N)
+        .map(|i| {
+            let t = i as f64 / FS as f64;
+            hz.iter()
+                .map(|f| amplitude * (2.0 * std::f64::consts::PI * *f as f64 * t).sin())
+                .sum::<f64>() as i16
+        })
+        .collect()
+}
+
+/// Signatures raised over `frames` identical frames.
+fn events(frame: &[i16], frames: usize) -> Vec<(usize, AcousticSignature)> {
+    let mut analyzer = AcousticAnalyzer::new(config());
+    (0..frames)
+        .flat_map(|i| {
+            analyzer
+                .process(frame)
+                .events
+                .into_iter()
+                .map(move |e| (i, e.signature))
+        })
+        .collect()
+}
+
+#[test]
+fn level_is_mean_power_in_whole_db() {
+    let mut analyzer = AcousticAnalyzer::new(config());
+
+    // 10·log10(100²) = 40; the linear mantissa rounds down.
+    let loud = analyzer.process(&[100; N]);
+    assert_eq!(loud.level_db, 39);
+    assert_eq!(loud.surplus_db, 9);
+    assert_eq!(analyzer.process(&[1; N]).level_db, 0);
+    assert_eq!(analyzer.process(&[]).level_db, 0);
+
+    let mut config = config();
+    config.mic_offset_db = 94;
+    let mut calibrated = AcousticAnalyzer::new(config);
+    assert_eq!(calibrated.process(&[100; N]).level_db, 39 + 94);
+}
+
+#[test]
+fn goertzel_bin_matches_a_pure_tone() {
+    let mut analyzer = AcousticAnalyzer::new(config());
+    let features = analyzer.process(&tones(&[400], 10_000.0));
+
+    // A 10 000-count sine has a mean power of 77 dB.
+    assert_eq!(features.peak_hz, 400);
+    let band = ACOUSTIC_BANDS_HZ.iter().position(|hz| *hz == 400).unwrap();
+    assert!((features.band_db[band] - 77).abs() <= 1);
+    assert!((features.level_db - 77).abs() <= 1);
+    assert!(features.band_db[band] - features.band_db[ACOUSTIC_BAND_COUNT - 1] > 40);
+}
+
+#[test]
+fn piping_tone_fires_once_after_sustain_frames() {
+    assert_eq!(
+        events(&tones(&[400], 10_000.0), 5),
+        [(2, AcousticSignature::QueenPiping)]
+    );
+}
+
+#[test]
+fn broadband_rise_is_a_queenless_roar() {
+    assert_eq!(
+        events(&tones(&ACOUSTIC_BANDS_HZ, 2_000.0), 3),
+        [(2, AcousticSignature::QueenlessRoar)]
+    );
+}
+
+#[test]
+fn buzz_band_rise_is_pre_swarm_buzz() {
+    let buzz = tones(&[200, 250, 300, 350, 400, 450, 500], 3_000.0);
+    assert_eq!(events(&buzz, 3), [(2, AcousticSignature::PreSwarmBuzz)]);
+}
+
+#[test]
+fn quiet_frame_resets_the_streak() {
+    let mut analyzer = AcousticAnalyzer::new(config());
+    let piping = tones(&[400], 10_000.0);
+
+    analyzer.process(&piping);
+    analyzer.process(&piping);
+    assert!(analyzer.process(&[0; N]).events.is_empty());
+    assert!(analyzer.process(&piping).events.is_empty());
+    assert!(analyzer.process(&piping).events.is_empty());
+    assert_eq!(analyzer.process(&piping).events.len(), 1);
+}
@@ -0,0 +1,152 @@
+mod common;
+
+use hive_shard_runtime::band::BandState::{self, Green, Red, Yellow};
+use hive_shard_runtime::band::{BandDebouncer, BandThresholds};
+
+use common::{config, green};
+
+/// Three of the last five samples, as in the fixture.
+fn thresholds() -> BandThresholds {
+    config().bands
+}
+
+/// Feeds `raw` samples starting from `current` and returns the debounced
+/// state after each one.
+fn debounce(
+    debouncer: &mut BandDebouncer,
+    mut current: BandState,
+    raw: &[BandState],
+) -> Vec<BandState> {
+    let thresholds = thresholds();
+    raw.iter()
+        .map(|&sample| {
+            current = debouncer.update(current, sample, &thresholds);
+            current
+        })
+        .collect()
+}
+
+/// A debouncer whose window is full of raw `band` samples.
+fn settled(band: BandState) -> BandDebouncer {
+    let mut debouncer = BandDebouncer::new();
+    assert_eq!(debounce(&mut debouncer, band, &[band; 5])[4], band);
+    debouncer
+}
+
+#[test]
+fn debounce_table() {
+    let cases: &[(&str, BandState, &[BandState], &[BandState])] = &[
+        (
+            "two of five yellow stays green",
+            Green,
+            &[Yellow, Yellow, Green],
+            &[Green, Green, Green],
+        ),
+        (
+            "exactly three of five yellow escalates",
+            Green,
+            &[Yellow, Green, Yellow, Yellow],
+            &[Green, Green, Green, Yellow],
+        ),
+        (
+            "samples older than five fall out of the ring",
+            Green,
+            &[Yellow, Yellow, Green, Green, Green, Yellow],
+            &[Green; 6],
+        ),
+        ("red escalates on one sample", Green, &[Red], &[Red]),
+        (
+            "yellow needs three green samples to clear",
+            Yellow,
+            &[Green, Yellow, Green, Green],
+            &[Yellow, Yellow, Yellow, Green],
+        ),
+        (
+            "red clears to yellow while yellow samples remain",
+            Red,
+            &[Yellow, Yellow, Yellow],
+            &[Red, Red, Yellow],
+        ),
+        (
+            "red clears straight to green on three green samples",
+            Red,
+            &[Green, Green, Green],
+            &[Red, Red, Green],
+        ),
+    ];
+
+    for (name, start, raw, expected) in cases {
+        let mut debouncer = settled(*start);
+        assert_eq!(debounce(&mut debouncer, *start, raw), *expected, "{name}");
+    }
+}
+
+#[test]
+fn window_not_yet_full_needs_every_sample_seen() {
+    // Until `debounce_m` samples are in, N is capped at the samples seen.
+    let mut debouncer = BandDebouncer::new();
+    assert_eq!(debounce(&mut debouncer, Green, &[Yellow]), [Yellow]);
+
+    let mut debouncer = BandDebouncer::new();
+    assert_eq!(
+        debounce(&mut debouncer, Green, &[Green, Yellow, Yellow, Yellow]),
+        [Green, Green, Green, Yellow]
+    );
+}
+
+#[test]
+fn hysteresis_table() {
+    let bands = thresholds();
+    // Yellow above 36 °C and below 33 °C, red above 38 °C; one degree of
+    // hysteresis. Humidity: yellow above 80 %, two points of hysteresis.
+    let cases: &[(BandState, i16, u8, BandState)] = &[
+        (Green, 36, 60, Green),
+        (Green, 37, 60, Yellow),
+        (Yellow, 36, 60, Yellow),
+        (Yellow, 35, 60, Green),
+        (Yellow, 33, 60, Yellow),
+        (Yellow, 34, 60, Green),
+        (Green, 33, 60, Green),
+        (Green, 32, 60, Yellow),
+        (Yellow, 38, 60, Yellow),
+        (Yellow, 39, 60, Red),
+        (Red, 38, 60, Red),
+        (Red, 37, 60, Yellow),
+        (Red, 35, 60, Green),
+        (Green, 34, 80, Green),
+        (Green, 34, 81, Yellow),
+        (Yellow, 34, 79, Yellow),
+        (Yellow, 34, 78, Green),
+    ];
+
+    for &(current, temp, humidity, expected) in cases {
+        let mut snapshot = green();
+        snapshot.brood_temp_c = temp;
+        snapshot.brood_humidity_pct = humidity;
+        assert_eq!(
+            current.evaluate(&snapshot, &bands),
+            expected,
+            "{current:?} at {temp} °C, {humidity} %"
+        );
+    }
+}
+
+#[test]
+fn boundary_noise_does_not_flap() {
+    let bands = thresholds();
+    let mut debouncer = settled(Green);
+    let mut current = Green;
+    let mut snapshot = green();
+
+    // Push into yellow, then jitter within the hysteresis margin.
+    let mut seen = Vec::new();
+    for temp in [37, 37, 37, 36, 37, 36, 36, 37, 36, 36] {
+        snapshot.brood_temp_c = temp;
+        let raw = current.evaluate(&snapshot, &bands);
+        current = debouncer.update(current, raw, &bands);
+        seen.push(current);
+    }
+
+    assert_eq!(seen[..2], [Green, Green]);
+    assert!(seen[2..].iter().all(|&band| band == Yellow));
+}
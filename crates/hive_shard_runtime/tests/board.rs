@@ -0,0 +1,153 @@
+mod common;
+
+use hive_shard_runtime::actuator::{ActuatorCommandFrame, ActuatorZone};
+use hive_shard_runtime::board::heater::HeaterRelay;
+use hive_shard_runtime::board::mock::{MockPin, MockPwm, RecordingBoard, MOCK_BOARD_HISTORY};
+use hive_shard_runtime::board::{ActuatorBoard, HalBoard};
+use hive_shard_runtime::timebase::TickCounter;
+
+use common::green;
+
+const MAX_DUTY: u16 = 1_000;
+
+fn board() -> HalBoard<MockPwm, MockPwm, MockPin> {
+    HalBoard::new(
+        MockPwm::new(MAX_DUTY),
+        MockPwm::new(MAX_DUTY),
+        HeaterRelay::new(MockPin::default(), 10, 4),
+        200,
+    )
+}
+
+fn ticks(n: u64) -> TickCounter {
+    let mut tick = TickCounter::new();
+    for _ in 0..n {
+        tick.increment();
+    }
+    tick
+}
+
+#[test]
+fn relay_duty_follows_the_proportional_band() {
+    let relay = HeaterRelay::new(MockPin::default(), 10, 4);
+
+    assert_eq!(relay.duty_pct(0, 20), 0);
+    assert_eq!(relay.duty_pct(34, 35), 0);
+    assert_eq!(relay.duty_pct(34, 34), 0);
+    assert_eq!(relay.duty_pct(34, 33), 25);
+    assert_eq!(relay.duty_pct(34, 32), 50);
+    assert_eq!(relay.duty_pct(34, 20), 100);
+}
+
+#[test]
+fn relay_switches_once_each_way_per_window() {
+    let mut relay = HeaterRelay::new(MockPin::default(), 10, 4);
+
+    let levels: Vec<bool> = (0..20)
+        .map(|t| {
+            relay.update(34, 32, ticks(t)).unwrap();
+            relay.is_on()
+        })
+        .collect();
+
+    // Half of a ten-tick window.
+    let window = [[true; 5], [false; 5]].concat();
+    assert_eq!(levels[..10], window);
+    assert_eq!(levels[10..], window);
+    // On, off, on, off: no chatter inside a window.
+    assert_eq!(relay.release().toggles, 4);
+}
+
+#[test]
+fn setpoint_zero_keeps_the_relay_off() {
+    let mut relay = HeaterRelay::new(MockPin::default(), 10, 4);
+    relay.update(34, 20, ticks(0)).unwrap();
+    assert!(relay.is_on());
+
+    relay.update(0, 20, ticks(1)).unwrap();
+    assert!(!relay.is_on());
+    assert!(!relay.release().high);
+}
+
+#[test]
+fn hal_board_maps_frame_onto_channels() {
+    let mut board = board();
+    let frame = ActuatorCommandFrame::brood_and_entrance(34, 40, 100);
+    let mut sensors = green();
+    sensors.brood_temp_c = 32;
+
+    board.apply(&frame, &sensors, ticks(0)).unwrap();
+
+    let (fan, led, heater) = board.release();
+    assert_eq!(fan.duty, 400);
+    // Half of the 200 lux full scale, rounded down through u16 fractions.
+    assert_eq!(led.duty, 499);
+    assert!(heater.is_on());
+}
+
+#[test]
+fn hal_board_clamps_led_to_full_scale() {
+    let mut board = board();
+    let frame = ActuatorCommandFrame::brood_and_entrance(0, 0, 5_000);
+
+    board.apply(&frame, &green(), ticks(0)).unwrap();
+
+    let (fan, led, heater) = board.release();
+    assert_eq!(fan.duty, 0);
+    assert_eq!(led.duty, MAX_DUTY);
+    assert!(!heater.is_on());
+}
+
+#[test]
+fn hal_board_reads_only_its_wired_zones() {
+    let mut board = board().with_zones(
+        ActuatorZone::HoneySuper,
+        ActuatorZone::Brood,
+        ActuatorZone::Entrance,
+    );
+    let frame = ActuatorCommandFrame::brood_and_entrance(34, 40, 200);
+
+    board.apply(&frame, &green(), ticks(0)).unwrap();
+
+    let (fan, led, heater) = board.release();
+    assert_eq!(fan.duty, 0);
+    assert_eq!(led.duty, 0);
+    assert!(!heater.is_on());
+}
+
+#[test]
+fn hal_board_all_off() {
+    let mut board = board();
+    let mut sensors = green();
+    sensors.brood_temp_c = 20;
+    let frame = ActuatorCommandFrame::brood_and_entrance(34, 60, 200);
+    board.apply(&frame, &sensors, ticks(0)).unwrap();
+
+    board.all_off().unwrap();
+
+    let (fan, led, heater) = board.release();
+    assert_eq!(fan.duty, 0);
+    assert_eq!(led.duty, 0);
+    assert!(!heater.release().high);
+}
+
+#[test]
+fn recording_board_keeps_the_latest_frames() {
+    let mut board = RecordingBoard::new();
+    assert!(board.last().is_none());
+
+    for t in 0..MOCK_BOARD_HISTORY as u64 + 3 {
+        let frame = ActuatorCommandFrame::brood_and_entrance(0, t as u8, 0);
+        board.apply(&frame, &green(), ticks(t)).unwrap();
+    }
+    board.all_off().unwrap();
+
+    assert_eq!(board.frames.len(), MOCK_BOARD_HISTORY);
+    assert_eq!(board.frames[0].0, 3);
+    let last = board.last().unwrap();
+    assert_eq!(
+        last.fan_duty_pct(ActuatorZone::Brood),
+        MOCK_BOARD_HISTORY as u8 + 2
+    );
+    assert_eq!(board.all_off_calls, 1);
+}
@@ -0,0 +1,35 @@
+#![allow(dead_code)]
+
+use hive_shard_runtime::actuator::ActuatorCommandFrame;
+use hive_shard_runtime::config::ShardConfig;
+use hive_shard_runtime::controller::NeuromorphicController;
+use hive_shard_runtime::sensor::{SensorFields, SensorSnapshot};
+
+/// One-minute control period, no power policy.
+pub fn config() -> ShardConfig {
+    serde_json::from_str(include_str!("../fixtures/shard_config.json")).unwrap()
+}
+
+/// A healthy brood-rearing colony, green on every band input.
+pub fn green() -> SensorSnapshot {
+    SensorSnapshot {
+        brood_temp_c: 34,
+        brood_humidity_pct: 60,
+        acoustic_surplus_db: 0,
+        daily_mortality_pct: 1,
+        hive_weight_kg_x10: 400,
+        forager_return_delta_pct: 0,
+        varroa_mites_per_100_bees: 1,
+        brood_gradient_c: 0,
+        missing: SensorFields::empty(),
+    }
+}
+
+/// Sends the same frame every period.
+pub struct Scripted(pub ActuatorCommandFrame);
+
+impl NeuromorphicController for Scripted {
+    fn step_neuromorphic(&mut self, _sensors: &SensorSnapshot) -> ActuatorCommandFrame {
+        self.0.clone()
+    }
+}
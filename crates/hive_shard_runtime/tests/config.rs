@@ -0,0 +1,23 @@
+use hive_shard_runtime::config::ShardConfig;
+
+fn fixture_without(section: &str, key: &str) -> ShardConfig {
+    let mut json: serde_json::Value =
+        serde_json::from_str(include_str!("fixtures/shard_config.json")).unwrap();
+    json[section].as_object_mut().unwrap().remove(key).unwrap();
+    serde_json::from_value(json).unwrap()
+}
+
+#[test]
+fn window_budgets_default_when_absent() {
+    let config = fixture_without("quota_profile", "max_spikes_in_window");
+    assert_eq!(config.quota_profile.max_spikes_in_window, 600_000);
+
+    let config = fixture_without("quota_profile", "max_energy_mj_in_window");
+    assert_eq!(config.quota_profile.max_energy_mj_in_window, 3_000);
+}
+
+#[test]
+fn fan_slew_defaults_when_absent() {
+    let config = fixture_without("limits", "max_fan_delta_pct_per_hour");
+    assert_eq!(config.limits.max_fan_delta_pct_per_hour, 20);
+}
@@ -0,0 +1,179 @@
+use hive_shard_runtime::drivers::hx711::{Hx711, Hx711Error, Hx711Gain, LoadCellCalibration};
+use hive_shard_runtime::drivers::mock::{
+    CountingOutputPin, I2cStep, NoopDelay, ScriptError, ScriptedI2c, ScriptedInputPin,
+};
+use hive_shard_runtime::drivers::sensirion::crc8;
+use hive_shard_runtime::drivers::sht3x::{Sht3x, Sht3xError, SHT3X_DEFAULT_ADDRESS};
+use hive_shard_runtime::drivers::sht4x::{Sht4x, Sht4xError, SHT4X_DEFAULT_ADDRESS};
+use hive_shard_runtime::drivers::{TempHumidity, TempHumiditySensor, WeightSensor};
+
+/// 25.00 °C and 50.00 %RH on the SHT3x transfer functions.
+const RAW_T: [u8; 2] = [0x66, 0x66];
+const RAW_RH: [u8; 2] = [0x80, 0x00];
+
+fn frame(t: [u8; 2], rh: [u8; 2]) -> [u8; 6] {
+    [t[0], t[1], crc8(&t), rh[0], rh[1], crc8(&rh)]
+}
+
+#[test]
+fn sensirion_crc_matches_datasheet_example() {
+    assert_eq!(crc8(&[0xBE, 0xEF]), 0x92);
+}
+
+#[test]
+fn sht3x_reads_single_shot_measurement() {
+    let a = SHT3X_DEFAULT_ADDRESS;
+    let i2c = ScriptedI2c::new(&[
+        I2cStep::write(a, &[0x24, 0x00]),
+        I2cStep::read(a, &frame(RAW_T, RAW_RH)),
+    ]);
+    let mut sensor = Sht3x::new(i2c, NoopDelay::default(), a);
+
+    let reading = sensor.read().unwrap();
+    assert_eq!(
+        reading,
+        TempHumidity {
+            temp_c_x100: 2500,
+            humidity_pct_x100: 5000,
+        }
+    );
+    let (i2c, delay) = sensor.release();
+    assert!(i2c.is_done());
+    assert!(delay.total_ns > 0);
+}
+
+#[test]
+fn sht3x_rejects_bad_crc() {
+    let a = SHT3X_DEFAULT_ADDRESS;
+    let mut corrupt = frame(RAW_T, RAW_RH);
+    corrupt[5] ^= 0xFF;
+    let i2c = ScriptedI2c::new(&[I2cStep::write(a, &[0x24, 0x00]), I2cStep::read(a, &corrupt)]);
+    let mut sensor = Sht3x::new(i2c, NoopDelay::default(), a);
+
+    assert!(matches!(sensor.read(), Err(Sht3xError::Crc)));
+    assert!(sensor.release().0.is_done());
+}
+
+#[test]
+fn sht3x_reports_nack() {
+    let a = SHT3X_DEFAULT_ADDRESS;
+    let i2c = ScriptedI2c::new(&[I2cStep::Fail(a)]);
+    let mut sensor = Sht3x::new(i2c, NoopDelay::default(), a);
+
+    assert!(matches!(
+        sensor.read(),
+        Err(Sht3xError::I2c(ScriptError::Injected))
+    ));
+    assert!(sensor.release().0.is_done());
+}
+
+#[test]
+fn sht4x_reads_and_clamps_humidity() {
+    let a = SHT4X_DEFAULT_ADDRESS;
+    // Full-scale RH is 119 % on the SHT4x transfer function.
+    let i2c = ScriptedI2c::new(&[
+        I2cStep::write(a, &[0xFD]),
+        I2cStep::read(a, &frame(RAW_T, [0xFF, 0xFF])),
+    ]);
+    let mut sensor = Sht4x::new(i2c, NoopDelay::default(), a);
+
+    let reading = sensor.read().unwrap();
+    assert_eq!(reading.temp_c_x100, 2500);
+    assert_eq!(reading.humidity_pct_x100, 10_000);
+    assert!(sensor.release().0.is_done());
+}
+
+#[test]
+fn sht4x_rejects_bad_crc() {
+    let a = SHT4X_DEFAULT_ADDRESS;
+    let mut corrupt = frame(RAW_T, RAW_RH);
+    corrupt[2] ^= 0x01;
+    let i2c = ScriptedI2c::new(&[I2cStep::write(a, &[0xFD]), I2cStep::read(a, &corrupt)]);
+    let mut sensor = Sht4x::new(i2c, NoopDelay::default(), a);
+
+    assert!(matches!(sensor.read(), Err(Sht4xError::Crc)));
+}
+
+#[test]
+fn sht4x_reports_nack_on_read() {
+    let a = SHT4X_DEFAULT_ADDRESS;
+    let i2c = ScriptedI2c::new(&[I2cStep::write(a, &[0xFD]), I2cStep::Fail(a)]);
+    let mut sensor = Sht4x::new(i2c, NoopDelay::default(), a);
+
+    assert!(matches!(
+        sensor.read(),
+        Err(Sht4xError::I2c(ScriptError::Injected))
+    ));
+    assert!(sensor.release().0.is_done());
+}
+
+/// DOUT levels for one conversion: a not-ready poll, the ready edge, then
+/// 24 data bits MSB first.
+fn conversion(raw: i32) -> [bool; 26] {
+    let mut levels = [false; 26];
+    levels[0] = true;
+    for bit in 0..24 {
+        levels[2 + bit] = (raw >> (23 - bit)) & 1 == 1;
+    }
+    levels
+}
+
+fn hx711(
+    raw: i32,
+    calibration: LoadCellCalibration,
+) -> Hx711<ScriptedInputPin, CountingOutputPin, NoopDelay> {
+    Hx711::new(
+        ScriptedInputPin::new(&conversion(raw)),
+        CountingOutputPin::default(),
+        NoopDelay::default(),
+        Hx711Gain::ChannelA128,
+        calibration,
+    )
+}
+
+#[test]
+fn hx711_clocks_out_signed_conversion() {
+    let calibration = LoadCellCalibration {
+        tare_offset: 0,
+        counts_per_kg: 1,
+    };
+    let mut adc = hx711(-82_000, calibration);
+
+    assert_eq!(adc.read_raw().unwrap(), -82_000);
+    let (_, sck, _) = adc.release();
+    // 24 data bits plus one pulse selecting channel A, gain 128.
+    assert_eq!(sck.rising_edges, 25);
+}
+
+#[test]
+fn hx711_applies_calibration() {
+    let calibration = LoadCellCalibration {
+        tare_offset: 8_000,
+        counts_per_kg: 2_000,
+    };
+    let mut adc = hx711(8_000 + 2_000 * 45, calibration);
+
+    assert_eq!(adc.read_kg_x10().unwrap(), 450);
+}
+
+#[test]
+fn hx711_accepts_reversed_cell() {
+    let calibration = LoadCellCalibration {
+        tare_offset: 8_000,
+        counts_per_kg: -2_000,
+    };
+    let mut adc = hx711(8_000 - 2_000 * 45, calibration);
+
+    assert_eq!(adc.read_kg_x10().unwrap(), 450);
+}
+
+#[test]
+fn hx711_rejects_zero_calibration() {
+    let calibration = LoadCellCalibration {
+        tare_offset: 0,
+        counts_per_kg: 0,
+    };
+    let mut adc = hx711(1_000, calibration);
+
+    assert!(matches!(adc.read_kg_x10(), Err(Hx711Error::Uncalibrated)));
+}
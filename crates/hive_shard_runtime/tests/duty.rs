@@ -0,0 +1,28 @@
+mod common;
+
+use hive_shard_runtime::actuator::{ActuatorCommandFrame, ActuatorZone};
+use hive_shard_runtime::HiveShardRuntime;
+
+use common::{config, green, Scripted};
+
+fn heater_on_minutes(duty_cycle_pct: u8, minutes: u32) -> u32 {
+    let mut config = config();
+    config.limits.max_actuator_duty_cycle_pct = duty_cycle_pct;
+    let controller = Scripted(ActuatorCommandFrame::brood_and_entrance(34, 0, 0));
+    let mut runtime = HiveShardRuntime::new(config, controller);
+    (0..minutes)
+        .filter(|_| runtime.step(&green()).heater_celsius(ActuatorZone::Brood) != 0)
+        .count() as u32
+}
+
+#[test]
+fn budget_switches_heater_off_for_the_rest_of_the_window() {
+    // 60 % of a one-hour window, and nothing more before the window rolls.
+    assert_eq!(heater_on_minutes(60, 36), 36);
+    assert_eq!(heater_on_minutes(60, 55), 36);
+}
+
+#[test]
+fn full_duty_cycle_never_switches_off() {
+    assert_eq!(heater_on_minutes(100, 120), 120);
+}
@@ -0,0 +1,117 @@
+mod common;
+
+use hive_shard_runtime::actuator::{
+    ActuatorChannel, ActuatorCommand, ActuatorCommandFrame, ActuatorKind, ActuatorZone,
+};
+use hive_shard_runtime::config::ShardConfig;
+use hive_shard_runtime::failsafe::{FailsafeMode, FailsafeReason};
+use hive_shard_runtime::limits::EntranceEnvelope;
+use hive_shard_runtime::sensor::SensorFields;
+use hive_shard_runtime::HiveShardRuntime;
+
+use common::{config, green, Scripted};
+
+/// 2024-06-01 20:30 UTC, half an hour after flight ends.
+const DUSK_MS: i64 = 1_717_273_800_000;
+/// 2024-06-01 12:00 UTC, mid-flight.
+const NOON_MS: i64 = 1_717_243_200_000;
+
+/// Reducer held at least 40 % open from 06:00 to 20:00 UTC.
+fn flight_config() -> ShardConfig {
+    let mut config = config();
+    config.actuation_caps.entrance = Some(EntranceEnvelope {
+        min_open_pct: 0,
+        flight_min_open_pct: 40,
+        flight_start_hour: 6,
+        flight_end_hour: 20,
+        utc_offset_min: 0,
+    });
+    config
+}
+
+fn reducer(frame: &ActuatorCommandFrame) -> Option<u8> {
+    frame.channels.iter().find_map(|c| match c.command {
+        ActuatorCommand::EntranceReducer { open_pct } if c.zone == ActuatorZone::Entrance => {
+            Some(open_pct)
+        }
+        _ => None,
+    })
+}
+
+#[test]
+fn reducer_closed_at_night_reopens_for_flight_in_observation_only() {
+    let config = flight_config();
+    let mut closed = ActuatorCommandFrame::default();
+    closed.set(
+        ActuatorZone::Entrance,
+        ActuatorCommand::EntranceReducer { open_pct: 0 },
+    );
+    let mut runtime = HiveShardRuntime::new(config, Scripted(closed));
+    runtime.anchor_utc(DUSK_MS);
+
+    let mut snapshot = green();
+    assert_eq!(reducer(&runtime.step(&snapshot)), Some(0));
+
+    // The brood probe drops out and the failsafe stops all actuation
+    // overnight.
+    snapshot.missing = SensorFields::BROOD_TEMP;
+    let mut steps = 1;
+    while runtime.failsafe_mode() != FailsafeMode::ObservationOnly {
+        runtime.step(&snapshot);
+        steps += 1;
+    }
+
+    // Steps until 06:00 send nothing; the first one at 06:00 reopens.
+    for _ in steps..9 * 60 + 29 {
+        assert_eq!(reducer(&runtime.step(&snapshot)), None);
+    }
+    assert_eq!(reducer(&runtime.step(&snapshot)), Some(40));
+    assert_eq!(reducer(&runtime.step(&snapshot)), None);
+    assert_eq!(runtime.failsafe_mode(), FailsafeMode::ObservationOnly);
+}
+
+#[test]
+fn floor_fits_a_frame_with_every_other_channel_set() {
+    let mut full = ActuatorCommandFrame::default();
+    for zone in ActuatorZone::ALL {
+        for kind in ActuatorKind::ALL {
+            let command = match kind {
+                ActuatorKind::Heater => ActuatorCommand::Heater { celsius: 34 },
+                ActuatorKind::Fan => ActuatorCommand::Fan { duty_pct: 0 },
+                ActuatorKind::Led => ActuatorCommand::Led { lux: 0 },
+                ActuatorKind::EntranceReducer if zone == ActuatorZone::Entrance => continue,
+                ActuatorKind::EntranceReducer => ActuatorCommand::EntranceReducer { open_pct: 50 },
+                ActuatorKind::VentFlap => ActuatorCommand::VentFlap { open_pct: 0 },
+                ActuatorKind::Feeder => ActuatorCommand::Feeder { syrup_ml: 0 },
+            };
+            assert!(full.set(zone, command));
+        }
+    }
+    let mut runtime = HiveShardRuntime::new(flight_config(), Scripted(full));
+    runtime.anchor_utc(NOON_MS);
+
+    let frame = runtime.step(&green());
+    assert_eq!(reducer(&frame), Some(40));
+    assert_eq!(runtime.failsafe_mode(), FailsafeMode::Normal);
+}
+
+#[test]
+fn frame_with_no_room_for_the_floor_trips_the_failsafe() {
+    let mut crowded = ActuatorCommandFrame::default();
+    while !crowded.channels.is_full() {
+        let led = ActuatorChannel {
+            zone: ActuatorZone::Brood,
+            command: ActuatorCommand::Led { lux: 100 },
+        };
+        crowded.channels.push(led).unwrap();
+    }
+    let mut runtime = HiveShardRuntime::new(flight_config(), Scripted(crowded));
+    runtime.anchor_utc(NOON_MS);
+
+    let frame = runtime.step(&green());
+    assert_eq!(runtime.failsafe_mode(), FailsafeMode::ObservationOnly);
+    assert_eq!(runtime.failsafe_reason(), Some(FailsafeReason::FrameOverflow));
+    // Only the floor goes out.
+    assert_eq!(frame.channels.len(), 1);
+    assert_eq!(reducer(&frame), Some(40));
+}
@@ -0,0 +1,214 @@
+mod common;
+
+use hive_shard_runtime::band::BandState::{self, Green, Red, Yellow};
+use hive_shard_runtime::failsafe::{
+    FailsafeEvent, FailsafeMachine, FailsafeMode, FailsafePolicy, FailsafeReason,
+};
+use hive_shard_runtime::quota::QuotaViolation;
+
+use common::config;
+
+const DWELL: u64 = 10;
+const GREEN_SAMPLES: u16 = 3;
+const PROBATION: u64 = 5;
+
+fn policy() -> FailsafePolicy {
+    let mut policy = config().failsafe;
+    policy.min_observation_dwell_ticks = DWELL as u32;
+    policy.recovery_green_samples = GREEN_SAMPLES;
+    policy.probation_ticks = PROBATION as u32;
+    policy
+}
+
+fn event(tick: u64, from: FailsafeMode, to: FailsafeMode, reason: FailsafeReason) -> FailsafeEvent {
+    FailsafeEvent {
+        tick,
+        from,
+        to,
+        reason,
+    }
+}
+
+/// Feeds `band` from `start` for `n` ticks and returns every event.
+fn feed(machine: &mut FailsafeMachine, start: u64, n: u64, band: BandState) -> Vec<FailsafeEvent> {
+    let policy = policy();
+    (start..start + n)
+        .filter_map(|now| machine.update(now, band, &policy))
+        .collect()
+}
+
+/// A machine that tripped at tick 0.
+fn tripped() -> FailsafeMachine {
+    let mut machine = FailsafeMachine::new();
+    machine.trip(0, FailsafeReason::RedBand).unwrap();
+    machine
+}
+
+#[test]
+fn yellow_degrades_and_green_streak_restores_normal() {
+    use FailsafeMode::*;
+    let mut machine = FailsafeMachine::new();
+    assert_eq!(machine.reason(), None);
+
+    assert_eq!(
+        feed(&mut machine, 0, 1, Yellow),
+        [event(0, Normal, Degraded, FailsafeReason::YellowBand)]
+    );
+    assert!(machine.mode().is_probationary());
+
+    // One short of the streak, then a yellow sample resets it.
+    assert!(feed(&mut machine, 1, 2, Green).is_empty());
+    assert!(feed(&mut machine, 3, 1, Yellow).is_empty());
+    assert!(feed(&mut machine, 4, 2, Green).is_empty());
+    assert_eq!(
+        feed(&mut machine, 6, 1, Green),
+        [event(6, Degraded, Normal, FailsafeReason::SustainedGreen)]
+    );
+}
+
+#[test]
+fn degraded_escalates_to_observation_only() {
+    use FailsafeMode::*;
+    let mut machine = FailsafeMachine::new();
+    feed(&mut machine, 0, 1, Yellow);
+
+    assert_eq!(
+        machine.trip(1, FailsafeReason::YellowBudgetExhausted),
+        Some(event(
+            1,
+            Degraded,
+            ObservationOnly,
+            FailsafeReason::YellowBudgetExhausted
+        ))
+    );
+    assert!(machine.mode().is_observation_only());
+    assert_eq!(
+        machine.reason(),
+        Some(FailsafeReason::YellowBudgetExhausted)
+    );
+}
+
+#[test]
+fn trip_while_observing_restarts_dwell_without_an_event() {
+    let mut machine = tripped();
+
+    assert_eq!(machine.trip(8, FailsafeReason::SensorFault), None);
+    assert_eq!(machine.reason(), Some(FailsafeReason::RedBand));
+    // Dwell now counts from tick 8.
+    assert!(feed(&mut machine, 9, 8, Green).is_empty());
+    assert_eq!(feed(&mut machine, 17, 1, Green).len(), 0);
+    assert_eq!(feed(&mut machine, 18, 1, Green).len(), 1);
+}
+
+#[test]
+fn recovery_waits_for_minimum_dwell() {
+    use FailsafeMode::*;
+    let mut machine = tripped();
+
+    // The streak is complete long before the dwell elapses.
+    assert!(feed(&mut machine, 1, DWELL - 1, Green).is_empty());
+    assert_eq!(machine.mode(), ObservationOnly);
+    assert_eq!(
+        feed(&mut machine, DWELL, 1, Green),
+        [event(
+            DWELL,
+            ObservationOnly,
+            Recovering,
+            FailsafeReason::SustainedGreen
+        )]
+    );
+}
+
+#[test]
+fn recovery_waits_for_green_streak() {
+    use FailsafeMode::*;
+    let mut machine = tripped();
+
+    // The dwell elapses during a yellow spell.
+    assert!(feed(&mut machine, 1, DWELL + 5, Yellow).is_empty());
+    assert!(feed(&mut machine, DWELL + 6, GREEN_SAMPLES as u64 - 1, Green).is_empty());
+    let now = DWELL + 5 + GREEN_SAMPLES as u64;
+    assert_eq!(
+        feed(&mut machine, now, 1, Green),
+        [event(
+            now,
+            ObservationOnly,
+            Recovering,
+            FailsafeReason::SustainedGreen
+        )]
+    );
+}
+
+#[test]
+fn relapse_during_probation_returns_to_observation_only() {
+    use FailsafeMode::*;
+    let mut machine = tripped();
+    feed(&mut machine, 1, DWELL, Green);
+    assert_eq!(machine.mode(), Recovering);
+
+    assert_eq!(
+        feed(&mut machine, DWELL + 1, 1, Yellow),
+        [event(
+            DWELL + 1,
+            Recovering,
+            ObservationOnly,
+            FailsafeReason::ProbationRelapse
+        )]
+    );
+    // The dwell restarts from the relapse.
+    assert!(feed(&mut machine, DWELL + 2, DWELL - 1, Green).is_empty());
+    assert_eq!(feed(&mut machine, 2 * DWELL + 1, 1, Green).len(), 1);
+}
+
+#[test]
+fn probation_completes_into_normal() {
+    use FailsafeMode::*;
+    let mut machine = tripped();
+    feed(&mut machine, 1, DWELL, Green);
+
+    assert!(feed(&mut machine, DWELL + 1, PROBATION - 1, Green).is_empty());
+    assert_eq!(
+        feed(&mut machine, DWELL + PROBATION, 1, Green),
+        [event(
+            DWELL + PROBATION,
+            Recovering,
+            Normal,
+            FailsafeReason::ProbationComplete
+        )]
+    );
+    assert_eq!(machine.reason(), Some(FailsafeReason::ProbationComplete));
+}
+
+#[test]
+fn red_sample_in_probation_relapses_like_yellow() {
+    let mut machine = tripped();
+    feed(&mut machine, 1, DWELL, Green);
+
+    let events = feed(&mut machine, DWELL + 1, 1, Red);
+    assert_eq!(events[0].reason, FailsafeReason::ProbationRelapse);
+}
+
+#[test]
+fn reason_codes_round_trip() {
+    let reasons = [
+        (FailsafeReason::RedBand, 0x01),
+        (FailsafeReason::CriticalBioload, 0x02),
+        (FailsafeReason::YellowBudgetExhausted, 0x03),
+        (FailsafeReason::SensorFault, 0x04),
+        (FailsafeReason::DeadlineOverrun, 0x05),
+        (FailsafeReason::ExposureIncident, 0x06),
+        (FailsafeReason::FrameOverflow, 0x07),
+        (FailsafeReason::Quota(QuotaViolation::WindowOps), 0x10),
+        (FailsafeReason::Quota(QuotaViolation::EnergyBudget), 0x13),
+        (FailsafeReason::YellowBand, 0x20),
+        (FailsafeReason::SustainedGreen, 0x21),
+        (FailsafeReason::ProbationRelapse, 0x22),
+        (FailsafeReason::ProbationComplete, 0x23),
+    ];
+    for (reason, code) in reasons {
+        assert_eq!(reason.reason_code(), code);
+        assert_eq!(FailsafeReason::from_reason_code(code), Some(reason));
+    }
+    assert_eq!(FailsafeReason::from_reason_code(0x00), None);
+    assert_eq!(FailsafeReason::from_reason_code(0xFF), None);
+}
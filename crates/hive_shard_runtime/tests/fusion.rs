@@ -0,0 +1,46 @@
+mod common;
+
+use hive_shard_runtime::fusion::{fuse, BroodProbe, MultiProbeSnapshot, ProbePosition};
+use hive_shard_runtime::sensor::SensorFields;
+
+use common::{config, green};
+
+fn probes(temps: &[i16]) -> MultiProbeSnapshot {
+    MultiProbeSnapshot {
+        base: green(),
+        probes: temps
+            .iter()
+            .enumerate()
+            .map(|(i, &temp)| BroodProbe {
+                position: ProbePosition {
+                    frame: 2 + i as u8,
+                    height_mm: 120,
+                },
+                temp_c: Some(temp),
+                humidity_pct: Some(60),
+            })
+            .collect(),
+    }
+}
+
+#[test]
+fn negative_outlier_deviation_keeps_the_median() {
+    let mut fusion = config().probe_fusion;
+    fusion.outlier_max_dev_c = -1;
+
+    let (snapshot, report) = fuse(&probes(&[34, 36, 34]), &fusion);
+
+    assert!(!snapshot.missing.contains(SensorFields::BROOD_TEMP));
+    assert_eq!(snapshot.brood_temp_c, 34);
+    assert_eq!(report.temp_probes_used, 2);
+    assert_eq!(report.rejected_temp, 0b010);
+}
+
+#[test]
+fn outlier_is_rejected_before_the_trimmed_mean() {
+    let (snapshot, report) = fuse(&probes(&[34, 35, 40, 33, 34]), &config().probe_fusion);
+
+    assert_eq!(snapshot.brood_temp_c, 34);
+    assert_eq!(report.temp_probes_used, 2);
+    assert_eq!(report.rejected_temp, 0b00100);
+}
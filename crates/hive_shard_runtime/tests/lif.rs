@@ -0,0 +1,98 @@
+mod common;
+
+use hive_shard_runtime::actuator::ActuatorZone;
+use hive_shard_runtime::controller::NeuromorphicController;
+use hive_shard_runtime::lif::{LifActivity, LifBlobError, LifController};
+
+use common::green;
+
+const HEADER_LEN: usize = 4 + 4 + 4 + 2 + 2 + 2 + 1 + 4;
+
+/// One brood-temperature input wired through one hidden neuron to the
+/// heater output, eight rate-coded timesteps.
+fn blob() -> Vec<u8> {
+    let mut blob = b"LIF1".to_vec();
+    blob.extend([1, 1, 8, 0]);
+    blob.extend(256i32.to_le_bytes());
+    blob.extend([31, 0]);
+    blob.extend(30i16.to_le_bytes());
+    blob.extend(36i16.to_le_bytes());
+    blob.push(50);
+    blob.extend(100u32.to_le_bytes());
+    // Input range, then w_in and w_out.
+    blob.extend(0i16.to_le_bytes());
+    blob.extend(34i16.to_le_bytes());
+    blob.extend(256i16.to_le_bytes());
+    for w in [256i16, 0, 0] {
+        blob.extend(w.to_le_bytes());
+    }
+    blob
+}
+
+fn load(blob: &[u8]) -> Result<LifController, LifBlobError> {
+    LifController::from_blob(blob)
+}
+
+#[test]
+fn loads_and_steps_a_network() {
+    let mut controller = load(&blob()).unwrap();
+
+    let frame = controller.step_neuromorphic(&green());
+
+    // Full-scale input spikes on seven of eight steps; each one drives
+    // the heater neuron over threshold.
+    assert_eq!(frame.heater_celsius(ActuatorZone::Brood), 35);
+    assert_eq!(frame.fan_duty_pct(ActuatorZone::Brood), 0);
+    assert_eq!(frame.led_lux(ActuatorZone::Entrance), 0);
+    assert_eq!(
+        controller.activity(),
+        LifActivity {
+            input_spikes: 7,
+            hidden_spikes: 7,
+            output_spikes: 7,
+            synaptic_ops: 7 + 7 * 3,
+        }
+    );
+}
+
+#[test]
+fn rejects_bad_magic() {
+    let mut blob = blob();
+    blob[3] = b'2';
+    assert!(matches!(load(&blob), Err(LifBlobError::BadMagic)));
+}
+
+#[test]
+fn rejects_truncated_header() {
+    let blob = blob();
+    for len in [0, 3, 6, HEADER_LEN - 1] {
+        assert!(
+            matches!(load(&blob[..len]), Err(LifBlobError::Truncated)),
+            "{len} bytes"
+        );
+    }
+}
+
+#[test]
+fn rejects_neuron_counts_that_do_not_match_the_payload() {
+    // Two hidden neurons need more weights than the blob carries.
+    let mut blob = blob();
+    blob[5] = 2;
+    assert!(matches!(load(&blob), Err(LifBlobError::Truncated)));
+
+    // No hidden neurons leave the weights unread.
+    let mut blob = self::blob();
+    blob[5] = 0;
+    assert!(matches!(load(&blob), Err(LifBlobError::TrailingBytes)));
+}
+
+#[test]
+fn rejects_oversized_network_and_unknown_encoding() {
+    let mut blob = blob();
+    blob[5] = 33;
+    assert!(matches!(load(&blob), Err(LifBlobError::TooManyNeurons)));
+
+    let mut blob = self::blob();
+    blob[7] = 2;
+    assert!(matches!(load(&blob), Err(LifBlobError::BadEncoding)));
+}
@@ -0,0 +1,152 @@
+mod common;
+
+use core::convert::Infallible;
+
+use ed25519_dalek::{Signer, SigningKey};
+use hive_shard_runtime::actuator::ActuatorCommandFrame;
+use hive_shard_runtime::band::BandState;
+use hive_shard_runtime::failsafe::{FailsafeMode, FailsafeReason};
+use hive_shard_runtime::persist::{NonVolatileStore, CHECKPOINT_LEN};
+use hive_shard_runtime::update::{ConfigUpdateError, SignedConfig};
+use hive_shard_runtime::HiveShardRuntime;
+
+use common::{config, green, Scripted};
+
+/// Checkpoint slots held in RAM, surviving a "reboot" of the runtime.
+struct MemoryStore {
+    slots: [[u8; CHECKPOINT_LEN]; 4],
+}
+
+impl MemoryStore {
+    fn erased() -> Self {
+        Self {
+            slots: [[0xFF; CHECKPOINT_LEN]; 4],
+        }
+    }
+}
+
+impl NonVolatileStore for MemoryStore {
+    type Error = Infallible;
+
+    fn slots(&self) -> u8 {
+        self.slots.len() as u8
+    }
+
+    fn read_slot(&mut self, slot: u8, buf: &mut [u8; CHECKPOINT_LEN]) -> Result<(), Infallible> {
+        *buf = self.slots[slot as usize];
+        Ok(())
+    }
+
+    fn write_slot(&mut self, slot: u8, data: &[u8; CHECKPOINT_LEN]) -> Result<(), Infallible> {
+        self.slots[slot as usize] = *data;
+        Ok(())
+    }
+}
+
+fn runtime() -> HiveShardRuntime<Scripted> {
+    HiveShardRuntime::new(config(), Scripted(ActuatorCommandFrame::default()))
+}
+
+#[test]
+fn red_brownout_resumes_in_observation_only() {
+    let mut store = MemoryStore::erased();
+    let mut runtime = runtime();
+
+    let mut snapshot = green();
+    runtime.step(&snapshot);
+    assert!(runtime.checkpoint(&mut store).unwrap());
+
+    // The brood chills well inside the checkpoint interval.
+    for temp in [31, 28, 25, 25, 25, 25, 25] {
+        snapshot.brood_temp_c = temp;
+        runtime.step(&snapshot);
+        runtime.checkpoint(&mut store).unwrap();
+    }
+    assert_eq!(runtime.failsafe_mode(), FailsafeMode::ObservationOnly);
+
+    // Power is lost without an orderly shutdown.
+    drop(runtime);
+    let restored = HiveShardRuntime::restore(
+        config(),
+        Scripted(ActuatorCommandFrame::default()),
+        &mut store,
+    )
+    .unwrap();
+
+    assert_eq!(restored.band_state(), BandState::Red);
+    assert_eq!(restored.failsafe_mode(), FailsafeMode::ObservationOnly);
+    assert_eq!(restored.failsafe_reason(), Some(FailsafeReason::RedBand));
+}
+
+#[test]
+fn unchanged_state_waits_for_the_interval() {
+    let mut store = MemoryStore::erased();
+    let mut runtime = runtime();
+
+    runtime.step(&green());
+    assert!(runtime.checkpoint(&mut store).unwrap());
+    runtime.step(&green());
+    assert!(!runtime.checkpoint(&mut store).unwrap());
+}
+
+fn signed(signing: &SigningKey, version: u64) -> (Vec<u8>, [u8; 64]) {
+    let payload = serde_json::to_vec(&SignedConfig {
+        version,
+        config: config(),
+    })
+    .unwrap();
+    let signature = signing.sign(&payload).to_bytes();
+    (payload, signature)
+}
+
+#[test]
+fn reboot_keeps_rejecting_versions_below_the_checkpointed_one() {
+    let signing = SigningKey::from_bytes(&[7; 32]);
+    let public = signing.verifying_key().to_bytes();
+    let mut store = MemoryStore::erased();
+
+    let mut runtime = runtime();
+    runtime.provision_update_key(&public, 3).unwrap();
+    let (v7, v7_signature) = signed(&signing, 7);
+    assert_eq!(runtime.apply_config_update(&v7, &v7_signature), Ok(7));
+    runtime.shutdown(&mut store).unwrap();
+
+    // The firmware boots its built-in version 3 config again.
+    let mut rebooted = HiveShardRuntime::restore(
+        config(),
+        Scripted(ActuatorCommandFrame::default()),
+        &mut store,
+    )
+    .unwrap();
+    rebooted.provision_update_key(&public, 3).unwrap();
+    assert_eq!(rebooted.config_version(), Some(3));
+
+    // An older signed update is not replayed in between.
+    let (v4, v4_signature) = signed(&signing, 4);
+    assert_eq!(
+        rebooted.apply_config_update(&v4, &v4_signature),
+        Err(ConfigUpdateError::Rollback {
+            installed: 7,
+            offered: 4
+        })
+    );
+    // Checkpoints written before the resend keep the floor.
+    rebooted.step(&green());
+    assert!(rebooted.checkpoint(&mut store).unwrap());
+    let mut again = HiveShardRuntime::restore(
+        config(),
+        Scripted(ActuatorCommandFrame::default()),
+        &mut store,
+    )
+    .unwrap();
+    let checkpoint = again.restored_checkpoint().unwrap();
+    assert!(!checkpoint.clean);
+    assert_eq!(checkpoint.config_version, Some(7));
+    again.provision_update_key(&public, 3).unwrap();
+    assert!(again.apply_config_update(&v4, &v4_signature).is_err());
+
+    // The version that was running may be sent again, once.
+    assert_eq!(again.apply_config_update(&v7, &v7_signature), Ok(7));
+    assert_eq!(again.config_version(), Some(7));
+    assert!(again.apply_config_update(&v7, &v7_signature).is_err());
+}
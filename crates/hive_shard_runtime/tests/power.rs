@@ -0,0 +1,169 @@
+mod common;
+
+use std::cell::Cell;
+
+use hive_shard_runtime::actuator::{ActuatorCommandFrame, ActuatorKind, ActuatorZone};
+use hive_shard_runtime::controller::NeuromorphicController;
+use hive_shard_runtime::power::{LoadShed, PowerManager, PowerMode, PowerPolicy, PowerState};
+use hive_shard_runtime::sensor::SensorSnapshot;
+use hive_shard_runtime::timebase::TickCounter;
+use hive_shard_runtime::HiveShardRuntime;
+
+use common::{config, green};
+
+/// 10 Wh battery with a 20 % night reserve; LEDs shed below 50 %, the
+/// heater below 30 %.
+fn policy() -> PowerPolicy {
+    PowerPolicy {
+        battery_capacity_mwh: 10_000,
+        baseline_load_mw: 200,
+        night_reserve_hours: 10,
+        conserve_below_soc_pct: 40,
+        conserve_inference_interval: 4,
+        harvest_surplus_mw: 1_000,
+        hysteresis_pct: 5,
+        shed: [
+            LoadShed {
+                actuator: ActuatorKind::Led,
+                below_soc_pct: 50,
+            },
+            LoadShed {
+                actuator: ActuatorKind::Heater,
+                below_soc_pct: 30,
+            },
+        ]
+        .into_iter()
+        .collect(),
+    }
+}
+
+fn battery(soc: u8) -> PowerState {
+    PowerState {
+        battery_soc_pct: soc,
+        harvest_mw: 0,
+    }
+}
+
+/// Mode after each reading, fed in order to one manager.
+fn modes(readings: &[PowerState]) -> Vec<PowerMode> {
+    let policy = policy();
+    let mut manager = PowerManager::new();
+    readings
+        .iter()
+        .map(|state| {
+            manager.update(*state, &policy);
+            manager.mode()
+        })
+        .collect()
+}
+
+#[test]
+fn reserve_covers_the_night_rounded_up() {
+    let mut policy = policy();
+    assert_eq!(policy.reserve_soc_pct(), 20);
+    policy.baseline_load_mw = 201;
+    assert_eq!(policy.reserve_soc_pct(), 21);
+}
+
+#[test]
+fn modes_follow_charge_with_hysteresis() {
+    use PowerMode::*;
+
+    assert_eq!(
+        modes(&[battery(60), battery(39), battery(44), battery(45)]),
+        [Normal, Conserve, Conserve, Normal]
+    );
+    assert_eq!(
+        modes(&[battery(20), battery(25), battery(26), battery(50)]),
+        [Reserve, Reserve, Conserve, Normal]
+    );
+}
+
+#[test]
+fn harvest_surplus_lifts_conserve_but_not_reserve() {
+    use PowerMode::*;
+    let sunny = |soc| PowerState {
+        battery_soc_pct: soc,
+        harvest_mw: 1_500,
+    };
+
+    assert_eq!(modes(&[sunny(30), sunny(15)]), [Normal, Reserve]);
+}
+
+#[test]
+fn inference_is_gated_by_mode() {
+    let policy = policy();
+    let mut manager = PowerManager::new();
+    let admitted = |manager: &PowerManager| {
+        let mut tick = TickCounter::new();
+        (0..8)
+            .filter(|_| {
+                let admitted = manager.admits_inference(tick, &policy);
+                tick.increment();
+                admitted
+            })
+            .count()
+    };
+
+    assert_eq!(admitted(&manager), 8);
+    manager.update(battery(35), &policy);
+    assert_eq!(admitted(&manager), 2);
+    manager.update(battery(10), &policy);
+    assert_eq!(admitted(&manager), 0);
+}
+
+#[test]
+fn loads_are_shed_in_threshold_order() {
+    let policy = policy();
+    let mut manager = PowerManager::new();
+    let shed = |manager: &PowerManager| {
+        let mut frame = ActuatorCommandFrame::brood_and_entrance(34, 40, 100);
+        manager.shed(&mut frame, &policy);
+        (
+            frame.heater_celsius(ActuatorZone::Brood),
+            frame.fan_duty_pct(ActuatorZone::Brood),
+            frame.led_lux(ActuatorZone::Entrance),
+        )
+    };
+
+    // Nothing is shed before the first reading.
+    assert_eq!(shed(&manager), (34, 40, 100));
+    manager.update(battery(50), &policy);
+    assert_eq!(shed(&manager), (34, 40, 100));
+    manager.update(battery(49), &policy);
+    assert_eq!(shed(&manager), (34, 40, 0));
+    manager.update(battery(29), &policy);
+    assert_eq!(shed(&manager), (0, 40, 0));
+}
+
+/// Counts how often the runtime lets it run.
+struct Counting<'a>(&'a Cell<u32>);
+
+impl NeuromorphicController for Counting<'_> {
+    fn step_neuromorphic(&mut self, _sensors: &SensorSnapshot) -> ActuatorCommandFrame {
+        self.0.set(self.0.get() + 1);
+        ActuatorCommandFrame::brood_and_entrance(34, 40, 100)
+    }
+}
+
+#[test]
+fn low_charge_gates_the_controller() {
+    let mut config = config();
+    config.power = Some(policy());
+    let runs = Cell::new(0);
+    let mut runtime = HiveShardRuntime::new(config, Counting(&runs));
+
+    runtime.update_power(battery(35));
+    for _ in 0..8 {
+        let frame = runtime.step(&green());
+        assert_eq!(frame.led_lux(ActuatorZone::Entrance), 0);
+    }
+    assert_eq!(runtime.power_mode(), PowerMode::Conserve);
+    assert_eq!(runs.get(), 2);
+
+    runtime.update_power(battery(10));
+    for _ in 0..8 {
+        assert!(runtime.step(&green()).channels.is_empty());
+    }
+    assert_eq!(runs.get(), 2);
+}
@@ -0,0 +1,179 @@
+mod common;
+
+use hive_shard_runtime::limits::{QuotaProfile, ShardLimits};
+use hive_shard_runtime::quota::{QuotaLedger, QuotaUsage, QuotaViolation, SlidingWindow};
+use hive_shard_runtime::timebase::TickCounter;
+
+use common::config;
+
+fn quota(max_ops: u32, max_spikes: u32, max_energy_mj: u32) -> QuotaProfile {
+    QuotaProfile {
+        window_ticks: 60,
+        max_ops_in_window: max_ops,
+        max_spikes_in_window: max_spikes,
+        max_energy_mj_in_window: max_energy_mj,
+    }
+}
+
+fn limits() -> ShardLimits {
+    config().limits
+}
+
+fn clock_at(ticks: u64) -> TickCounter {
+    let mut clock = TickCounter::with_period_ms(60_000);
+    for _ in 0..ticks {
+        clock.increment();
+    }
+    clock
+}
+
+fn usage(spikes: u32, energy_mj: u32) -> QuotaUsage {
+    QuotaUsage {
+        inferences: 1,
+        spikes,
+        energy_mj,
+    }
+}
+
+#[test]
+fn sliding_window_expires_whole_buckets() {
+    // Four buckets of two ticks each.
+    let mut window = SlidingWindow::<4>::new(8);
+    window.add(0, 3);
+    window.add(1, 2);
+    window.add(6, 1);
+
+    assert_eq!(window.total(7), 6);
+    assert_eq!(window.total(8), 1);
+    assert_eq!(window.total(14), 0);
+}
+
+#[test]
+fn short_window_does_not_span_every_bucket() {
+    let mut window = SlidingWindow::<16>::new(3);
+    window.add(0, 1);
+
+    assert_eq!(window.total(2), 1);
+    assert_eq!(window.total(3), 0);
+}
+
+#[test]
+fn ops_are_admitted_up_to_the_limit_until_the_window_rolls() {
+    let limits = limits();
+    let quota = quota(3, u32::MAX, u32::MAX);
+    let clock = clock_at(0);
+    let mut ledger = QuotaLedger::new(&quota, &clock);
+
+    for _ in 0..3 {
+        assert_eq!(
+            limits.check_and_debit_quota(&mut ledger, clock, &quota),
+            Ok(())
+        );
+    }
+    assert_eq!(
+        limits.check_and_debit_quota(&mut ledger, clock, &quota),
+        Err(QuotaViolation::WindowOps)
+    );
+    assert_eq!(ledger.remaining_ops(59, &quota), 0);
+
+    let later = clock_at(60);
+    assert_eq!(ledger.remaining_ops(60, &quota), 3);
+    assert_eq!(
+        limits.check_and_debit_quota(&mut ledger, later, &quota),
+        Ok(())
+    );
+}
+
+#[test]
+fn inference_rate_is_limited_per_minute() {
+    let mut limits = limits();
+    limits.max_inferences_per_minute = 2;
+    let quota = quota(u32::MAX, u32::MAX, u32::MAX);
+    let clock = clock_at(0);
+    let mut ledger = QuotaLedger::new(&quota, &clock);
+
+    assert_eq!(
+        limits.check_and_debit_quota(&mut ledger, clock, &quota),
+        Ok(())
+    );
+    assert_eq!(
+        limits.check_and_debit_quota(&mut ledger, clock, &quota),
+        Ok(())
+    );
+    assert_eq!(
+        limits.check_and_debit_quota(&mut ledger, clock, &quota),
+        Err(QuotaViolation::InferenceRate)
+    );
+    assert_eq!(
+        limits.check_and_debit_quota(&mut ledger, clock_at(1), &quota),
+        Ok(())
+    );
+}
+
+#[test]
+fn spike_budget_is_exhausted_over_the_window() {
+    let limits = limits();
+    let quota = quota(u32::MAX, 1_000, u32::MAX);
+    let clock = clock_at(0);
+    let mut ledger = QuotaLedger::new(&quota, &clock);
+
+    assert_eq!(
+        limits.debit_step_usage(&mut ledger, clock, &usage(500, 0), &quota),
+        Ok(())
+    );
+    assert_eq!(
+        limits.debit_step_usage(&mut ledger, clock_at(1), &usage(500, 0), &quota),
+        Ok(())
+    );
+    assert_eq!(
+        limits.debit_step_usage(&mut ledger, clock_at(2), &usage(1, 0), &quota),
+        Err(QuotaViolation::SpikeBudget)
+    );
+    assert_eq!(
+        limits.debit_step_usage(&mut ledger, clock_at(64), &usage(500, 0), &quota),
+        Ok(())
+    );
+}
+
+#[test]
+fn spikes_over_the_per_period_ceiling_are_rejected() {
+    let limits = limits();
+    let quota = quota(u32::MAX, u32::MAX, u32::MAX);
+    let clock = clock_at(0);
+    let mut ledger = QuotaLedger::new(&quota, &clock);
+
+    let over = usage(limits.max_spikes_per_period + 1, 0);
+    assert_eq!(
+        limits.debit_step_usage(&mut ledger, clock, &over, &quota),
+        Err(QuotaViolation::SpikeBudget)
+    );
+}
+
+#[test]
+fn energy_budget_is_exhausted_over_the_window() {
+    let limits = limits();
+    let per_inference = limits.max_joules_per_inference_mj;
+    let quota = quota(u32::MAX, u32::MAX, 2 * per_inference);
+    let clock = clock_at(0);
+    let mut ledger = QuotaLedger::new(&quota, &clock);
+
+    for tick in 0..2 {
+        assert_eq!(
+            limits.debit_step_usage(
+                &mut ledger,
+                clock_at(tick),
+                &usage(0, per_inference),
+                &quota
+            ),
+            Ok(())
+        );
+    }
+    assert_eq!(
+        limits.debit_step_usage(&mut ledger, clock_at(2), &usage(0, 1), &quota),
+        Err(QuotaViolation::EnergyBudget)
+    );
+    assert_eq!(
+        limits.debit_step_usage(&mut ledger, clock, &usage(0, per_inference + 1), &quota),
+        Err(QuotaViolation::EnergyBudget)
+    );
+}
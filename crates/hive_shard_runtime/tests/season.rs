@@ -0,0 +1,80 @@
+mod common;
+
+use hive_shard_runtime::season::{Hemisphere, SeasonProfile, SeasonalThresholds};
+
+use common::config;
+
+/// December 1st and March 1st on the northern calendar.
+const DEC_1: u16 = 335;
+const MAR_1: u16 = 60;
+
+fn profile(day_of_year: u16, yellow_min_temp_c: i16, elevated_mites: u8) -> SeasonProfile {
+    let config = config();
+    let mut bands = config.bands;
+    bands.yellow_min_temp_c = yellow_min_temp_c;
+    let mut bioload = config.bioload_thresholds;
+    bioload.elevated_mites_per_100_bees = elevated_mites;
+    SeasonProfile {
+        day_of_year,
+        bands,
+        bioload,
+    }
+}
+
+/// A winter profile on December 1st and a spring one on March 1st.
+fn seasonal(hemisphere: Hemisphere) -> SeasonalThresholds {
+    SeasonalThresholds {
+        hemisphere,
+        profiles: [profile(MAR_1, 30, 5), profile(DEC_1, 10, 2)]
+            .into_iter()
+            .collect(),
+    }
+}
+
+/// Yellow minimum temperature and elevated mite count on `day`.
+fn resolve(seasonal: &SeasonalThresholds, day: u16) -> (i16, u8) {
+    let (bands, bioload) = seasonal.resolve(day).unwrap();
+    (bands.yellow_min_temp_c, bioload.elevated_mites_per_100_bees)
+}
+
+#[test]
+fn no_profiles_resolve_to_none() {
+    let empty = SeasonalThresholds {
+        hemisphere: Hemisphere::Northern,
+        profiles: Default::default(),
+    };
+    assert!(empty.resolve(100).is_none());
+}
+
+#[test]
+fn interpolates_across_the_year_end() {
+    let northern = seasonal(Hemisphere::Northern);
+
+    // Ninety days from December 1st to March 1st.
+    assert_eq!(resolve(&northern, DEC_1), (10, 2));
+    assert_eq!(resolve(&northern, 350), (13, 2));
+    assert_eq!(resolve(&northern, 1), (16, 3));
+    assert_eq!(resolve(&northern, MAR_1), (30, 5));
+    // A leap year's 366th day wraps onto January 1st.
+    assert_eq!(resolve(&northern, 366), resolve(&northern, 1));
+}
+
+#[test]
+fn interpolates_between_profiles_within_the_year() {
+    let northern = seasonal(Hemisphere::Northern);
+
+    // 140 of the 275 days from March 1st to December 1st.
+    assert_eq!(resolve(&northern, 200), (20, 4));
+}
+
+#[test]
+fn southern_sites_shift_half_a_year() {
+    let southern = seasonal(Hemisphere::Southern);
+    let northern = seasonal(Hemisphere::Northern);
+
+    // June 2nd in the south is the northern December 1st.
+    assert_eq!(resolve(&southern, 153), (10, 2));
+    assert_eq!(resolve(&southern, 243), (30, 5));
+    assert_eq!(resolve(&southern, 1), resolve(&northern, 183));
+    assert_eq!(resolve(&southern, 200), resolve(&northern, 17));
+}
@@ -0,0 +1,40 @@
+mod common;
+
+use hive_shard_runtime::actuator::ActuatorCommandFrame;
+use hive_shard_runtime::band::BandState;
+use hive_shard_runtime::failsafe::{FailsafeMode, FailsafeReason};
+use hive_shard_runtime::HiveShardRuntime;
+
+use common::{config, green, Scripted};
+
+#[test]
+fn implausible_probe_at_boot_is_a_sensor_fault_not_red() {
+    let controller = Scripted(ActuatorCommandFrame::brood_and_entrance(34, 20, 0));
+    let mut runtime = HiveShardRuntime::new(config(), controller);
+
+    // Probe unplugged: the ADC floor is far below the plausible range and
+    // there is no earlier healthy reading to hold.
+    let mut unplugged = green();
+    unplugged.brood_temp_c = -40;
+    for _ in 0..5 {
+        let frame = runtime.step(&unplugged);
+        assert!(frame.channels.is_empty());
+    }
+
+    assert_eq!(runtime.band_state(), BandState::Green);
+    assert_eq!(runtime.failsafe_mode(), FailsafeMode::ObservationOnly);
+    assert_eq!(runtime.failsafe_reason(), Some(FailsafeReason::SensorFault));
+}
+
+#[test]
+fn cold_brood_with_a_healthy_probe_is_red() {
+    let mut runtime = HiveShardRuntime::new(config(), Scripted(ActuatorCommandFrame::default()));
+    let mut cold = green();
+    cold.brood_temp_c = 25;
+    for _ in 0..5 {
+        runtime.step(&cold);
+    }
+
+    assert_eq!(runtime.band_state(), BandState::Red);
+    assert_eq!(runtime.failsafe_reason(), Some(FailsafeReason::RedBand));
+}
@@ -0,0 +1,197 @@
+mod common;
+
+use ed25519_dalek::{Signer, SigningKey};
+use hive_shard_runtime::actuator::ActuatorCommandFrame;
+use hive_shard_runtime::config::ShardConfig;
+use hive_shard_runtime::power::{PowerMode, PowerPolicy, PowerState};
+use hive_shard_runtime::update::{ConfigUpdateError, SignedConfig};
+use hive_shard_runtime::HiveShardRuntime;
+
+use common::{config, Scripted};
+
+const INSTALLED: u64 = 3;
+
+fn operator() -> SigningKey {
+    SigningKey::from_bytes(&[7; 32])
+}
+
+fn runtime() -> HiveShardRuntime<Scripted> {
+    let mut runtime = HiveShardRuntime::new(config(), Scripted(ActuatorCommandFrame::default()));
+    runtime
+        .provision_update_key(&operator().verifying_key().to_bytes(), INSTALLED)
+        .unwrap();
+    runtime
+}
+
+fn sign(key: &SigningKey, payload: &[u8]) -> [u8; 64] {
+    key.sign(payload).to_bytes()
+}
+
+fn payload(version: u64, config: ShardConfig) -> Vec<u8> {
+    serde_json::to_vec(&SignedConfig { version, config }).unwrap()
+}
+
+/// A config that differs from the fixture in limits, bands and power.
+fn retuned() -> ShardConfig {
+    let mut config = config();
+    config.limits.max_inferences_per_minute += 1;
+    config.bands.yellow_min_temp_c += 1;
+    config.power = Some(PowerPolicy {
+        battery_capacity_mwh: 10_000,
+        baseline_load_mw: 200,
+        night_reserve_hours: 10,
+        conserve_below_soc_pct: 40,
+        conserve_inference_interval: 4,
+        harvest_surplus_mw: 1_000,
+        hysteresis_pct: 5,
+        shed: Default::default(),
+    });
+    config
+}
+
+/// Asserts the runtime still runs the fixture config at `INSTALLED`.
+fn assert_untouched(runtime: &mut HiveShardRuntime<Scripted>) {
+    let fixture = config();
+    assert_eq!(runtime.config_version(), Some(INSTALLED));
+    assert_eq!(
+        runtime.limits().max_inferences_per_minute,
+        fixture.limits.max_inferences_per_minute
+    );
+    assert_eq!(
+        runtime.active_band_thresholds().yellow_min_temp_c,
+        fixture.bands.yellow_min_temp_c
+    );
+    // Without a power policy the battery reading is ignored.
+    runtime.update_power(PowerState {
+        battery_soc_pct: 10,
+        harvest_mw: 0,
+    });
+    assert_eq!(runtime.power_mode(), PowerMode::Normal);
+}
+
+#[test]
+fn applies_a_newer_signed_config() {
+    let mut runtime = runtime();
+    let payload = payload(4, retuned());
+
+    assert_eq!(
+        runtime.apply_config_update(&payload, &sign(&operator(), &payload)),
+        Ok(4)
+    );
+    assert_eq!(runtime.config_version(), Some(4));
+    assert_eq!(
+        runtime.active_band_thresholds().yellow_min_temp_c,
+        retuned().bands.yellow_min_temp_c
+    );
+    runtime.update_power(PowerState {
+        battery_soc_pct: 10,
+        harvest_mw: 0,
+    });
+    assert_eq!(runtime.power_mode(), PowerMode::Reserve);
+}
+
+#[test]
+fn rejects_update_before_a_key_is_provisioned() {
+    let mut runtime = HiveShardRuntime::new(config(), Scripted(ActuatorCommandFrame::default()));
+    let payload = payload(4, retuned());
+
+    assert_eq!(
+        runtime.apply_config_update(&payload, &sign(&operator(), &payload)),
+        Err(ConfigUpdateError::NotProvisioned)
+    );
+}
+
+#[test]
+fn rejects_corrupted_signature() {
+    let mut runtime = runtime();
+    let payload = payload(4, retuned());
+    let mut signature = sign(&operator(), &payload);
+    signature[10] ^= 0x01;
+
+    assert_eq!(
+        runtime.apply_config_update(&payload, &signature),
+        Err(ConfigUpdateError::BadSignature)
+    );
+    assert_untouched(&mut runtime);
+}
+
+#[test]
+fn rejects_payload_altered_after_signing() {
+    let mut runtime = runtime();
+    let signed = payload(4, config());
+    let signature = sign(&operator(), &signed);
+    let altered = payload(4, retuned());
+
+    assert_eq!(
+        runtime.apply_config_update(&altered, &signature),
+        Err(ConfigUpdateError::BadSignature)
+    );
+    assert_untouched(&mut runtime);
+}
+
+#[test]
+fn rejects_payload_signed_by_another_key() {
+    let mut runtime = runtime();
+    let payload = payload(4, retuned());
+    let intruder = SigningKey::from_bytes(&[9; 32]);
+
+    assert_eq!(
+        runtime.apply_config_update(&payload, &sign(&intruder, &payload)),
+        Err(ConfigUpdateError::BadSignature)
+    );
+    assert_untouched(&mut runtime);
+}
+
+#[test]
+fn rejects_versions_not_newer_than_installed() {
+    let mut runtime = runtime();
+
+    for version in [INSTALLED - 1, INSTALLED] {
+        let payload = payload(version, retuned());
+        assert_eq!(
+            runtime.apply_config_update(&payload, &sign(&operator(), &payload)),
+            Err(ConfigUpdateError::Rollback {
+                installed: INSTALLED,
+                offered: version,
+            })
+        );
+    }
+    assert_untouched(&mut runtime);
+}
+
+#[test]
+fn rejects_malformed_payload_with_valid_signature() {
+    let mut runtime = runtime();
+
+    for payload in [
+        &b"not json"[..],
+        br#"{"version":4}"#,
+        br#"{"version":4,"config":{}}"#,
+    ] {
+        assert_eq!(
+            runtime.apply_config_update(payload, &sign(&operator(), payload)),
+            Err(ConfigUpdateError::Malformed)
+        );
+    }
+    assert_untouched(&mut runtime);
+}
+
+#[test]
+fn rejects_geometry_changes_without_applying_anything() {
+    let mut runtime = runtime();
+    let resized: [fn(&mut ShardConfig); 2] = [
+        |config| config.tick_period_ms *= 2,
+        |config| config.quota_profile.window_ticks += 1,
+    ];
+
+    for resize in resized {
+        let mut config = retuned();
+        resize(&mut config);
+        let payload = payload(4, config);
+        assert_eq!(
+            runtime.apply_config_update(&payload, &sign(&operator(), &payload)),
+            Err(ConfigUpdateError::GeometryChanged)
+        );
+    }
+    assert_untouched(&mut runtime);
+}
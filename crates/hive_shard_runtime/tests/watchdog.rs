@@ -0,0 +1,86 @@
+mod common;
+
+use hive_shard_runtime::actuator::ActuatorCommandFrame;
+use hive_shard_runtime::controller::{NeuromorphicController, StepUsage};
+use hive_shard_runtime::failsafe::{FailsafeMode, FailsafeReason};
+use hive_shard_runtime::sensor::SensorSnapshot;
+use hive_shard_runtime::watchdog::FakeClock;
+use hive_shard_runtime::HiveShardRuntime;
+
+use common::{config, green};
+
+const SPIKES_PER_STEP: u32 = 100;
+
+/// Takes the scripted time per step, in microseconds, on the shared clock.
+struct Slow<'a> {
+    clock: &'a FakeClock,
+    step_us: &'a [u64],
+    steps: usize,
+}
+
+impl NeuromorphicController for Slow<'_> {
+    fn step_neuromorphic(&mut self, _sensors: &SensorSnapshot) -> ActuatorCommandFrame {
+        self.clock.advance_us(self.step_us[self.steps]);
+        self.steps += 1;
+        ActuatorCommandFrame::brood_and_entrance(34, 20, 0)
+    }
+
+    fn step_usage(&self) -> StepUsage {
+        StepUsage {
+            spikes: SPIKES_PER_STEP,
+            ..StepUsage::default()
+        }
+    }
+}
+
+/// Runs one budgeted step per entry of `step_us` against the fixture's
+/// 5 ms deadline and three tolerated overruns; returns whether each step
+/// actuated.
+fn run<'a>(
+    clock: &'a FakeClock,
+    step_us: &'a [u64],
+) -> (HiveShardRuntime<Slow<'a>>, Vec<bool>) {
+    let controller = Slow {
+        clock,
+        step_us,
+        steps: 0,
+    };
+    let mut runtime = HiveShardRuntime::new(config(), controller);
+    let actuated = step_us
+        .iter()
+        .map(|_| !runtime.step_budgeted(&green(), clock).channels.is_empty())
+        .collect();
+    (runtime, actuated)
+}
+
+#[test]
+fn late_outputs_are_used_during_an_overrun_streak() {
+    let clock = FakeClock::new();
+    let (runtime, actuated) = run(&clock, &[6_000, 6_000]);
+
+    assert_eq!(actuated, [true, true]);
+    assert_eq!(runtime.watchdog().consecutive_overruns(), 2);
+    assert_eq!(runtime.failsafe_mode(), FailsafeMode::Normal);
+}
+
+#[test]
+fn an_on_time_step_resets_the_streak() {
+    let clock = FakeClock::new();
+    let (runtime, actuated) = run(&clock, &[6_000, 6_000, 1_000, 6_000, 6_000]);
+
+    assert!(actuated.iter().all(|a| *a));
+    assert_eq!(runtime.watchdog().consecutive_overruns(), 2);
+    assert_eq!(runtime.watchdog().total_overruns(), 4);
+    assert_eq!(runtime.failsafe_mode(), FailsafeMode::Normal);
+}
+
+#[test]
+fn the_streak_trips_and_the_tripping_step_is_charged() {
+    let clock = FakeClock::new();
+    let (runtime, actuated) = run(&clock, &[6_000, 6_000, 6_000]);
+
+    assert_eq!(actuated, [true, true, false]);
+    assert_eq!(runtime.failsafe_mode(), FailsafeMode::ObservationOnly);
+    assert_eq!(runtime.failsafe_reason(), Some(FailsafeReason::DeadlineOverrun));
+    assert_eq!(runtime.telemetry().spikes_in_window, 3 * SPIKES_PER_STEP);
+}
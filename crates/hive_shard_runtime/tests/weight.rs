@@ -0,0 +1,54 @@
+mod common;
+
+use hive_shard_runtime::actuator::ActuatorCommandFrame;
+use hive_shard_runtime::band::BandState;
+use hive_shard_runtime::failsafe::FailsafeMode;
+use hive_shard_runtime::sensor::SensorFields;
+use hive_shard_runtime::HiveShardRuntime;
+
+use common::{config, green, Scripted};
+
+#[test]
+fn starvation_floor_does_not_spend_yellow_budget() {
+    let controller = Scripted(ActuatorCommandFrame::default());
+    let mut runtime = HiveShardRuntime::new(config(), controller);
+    let budget_secs = runtime.yellow_budget_remaining_secs();
+
+    // Two kilograms a day off 35 kg leaves about four days of stores.
+    let mut snapshot = green();
+    for minute in 0..72 * 60 {
+        snapshot.hive_weight_kg_x10 = 350 - minute * 20 / (24 * 60);
+        runtime.step(&snapshot);
+    }
+
+    assert!(runtime.weight_trend().starvation_days().is_some());
+    assert_eq!(runtime.band_state(), BandState::Yellow);
+    assert_eq!(runtime.yellow_budget_remaining_secs(), budget_secs);
+    assert_eq!(runtime.failsafe_mode(), FailsafeMode::Normal);
+}
+
+#[test]
+fn hours_without_readings_keep_their_slot() {
+    let controller = Scripted(ActuatorCommandFrame::default());
+    let mut runtime = HiveShardRuntime::new(config(), controller);
+
+    // A steady 40 kg, then the scale drops out for a day and comes back
+    // two kilograms lighter.
+    let mut snapshot = green();
+    for _ in 0..24 * 60 {
+        runtime.step(&snapshot);
+    }
+    snapshot.missing = SensorFields::HIVE_WEIGHT;
+    for _ in 0..24 * 60 {
+        runtime.step(&snapshot);
+    }
+    snapshot.missing = SensorFields::empty();
+    snapshot.hive_weight_kg_x10 = 380;
+    for _ in 0..2 * 60 {
+        runtime.step(&snapshot);
+    }
+
+    // The gap still counts as a day: the last 24 clock hours hold one
+    // reading hour at 38 kg against a full day at 40 kg.
+    assert_eq!(runtime.weight_trend().net_kg_x10_per_day(), Some(-20));
+}
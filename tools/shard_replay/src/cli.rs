@@ -0,0 +1,96 @@
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::{anyhow, bail, Context};
+use clap::{Parser, ValueEnum};
+
+use hive_shard_runtime::config::ShardConfig;
+use hive_shard_runtime::lif::LifController;
+
+use crate::input::{read_records, InputFormat};
+use crate::trace::{diff_golden, replay, to_jsonl, NullController};
+
+/// Golden-file differences printed before giving up.
+const MAX_DIFF_LINES: usize = 20;
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, ValueEnum)]
+pub enum ControllerKind {
+    Null,
+    Lif,
+}
+
+#[derive(Parser, Debug)]
+#[command(name = "shard-replay")]
+pub struct Cli {
+    /// `ShardConfig` as JSON.
+    #[arg(short = 'c', long = "config")]
+    pub config: PathBuf,
+    /// Recorded snapshots, CSV or JSONL.
+    #[arg(short = 'i', long = "input")]
+    pub input: PathBuf,
+    /// Input format; inferred from the extension when omitted.
+    #[arg(long = "format", value_enum)]
+    pub format: Option<InputFormat>,
+    #[arg(long = "controller", value_enum, default_value = "null")]
+    pub controller: ControllerKind,
+    /// LIF1 weight blob for `--controller lif`.
+    #[arg(long = "lif-blob")]
+    pub lif_blob: Option<PathBuf>,
+    /// Trace output; stdout when omitted.
+    #[arg(short = 'o', long = "output")]
+    pub output: Option<PathBuf>,
+    /// Fail with a diff if the trace differs from this file.
+    #[arg(long = "golden")]
+    pub golden: Option<PathBuf>,
+    /// Overwrite the golden file with the new trace instead of comparing.
+    #[arg(long = "bless", requires = "golden")]
+    pub bless: bool,
+}
+
+pub fn run() -> anyhow::Result<()> {
+    let cli = Cli::parse();
+    let config: ShardConfig = serde_json::from_str(
+        &fs::read_to_string(&cli.config)
+            .with_context(|| format!("reading {}", cli.config.display()))?,
+    )?;
+    let format = cli
+        .format
+        .or_else(|| InputFormat::from_path(&cli.input))
+        .ok_or_else(|| anyhow!("cannot infer input format; pass --format"))?;
+    let records = read_records(&cli.input, format)?;
+
+    let rows = match cli.controller {
+        ControllerKind::Null => replay(config, NullController, &records)?,
+        ControllerKind::Lif => {
+            let path = cli
+                .lif_blob
+                .as_ref()
+                .ok_or_else(|| anyhow!("--controller lif needs --lif-blob"))?;
+            let blob = fs::read(path).with_context(|| format!("reading {}", path.display()))?;
+            let controller = LifController::from_blob(&blob)
+                .map_err(|e| anyhow!("{}: {e:?}", path.display()))?;
+            replay(config, controller, &records)?
+        }
+    };
+    let trace = to_jsonl(&rows)?;
+
+    match &cli.output {
+        Some(path) => fs::write(path, &trace)?,
+        None => print!("{trace}"),
+    }
+
+    if let Some(golden_path) = &cli.golden {
+        if cli.bless {
+            fs::write(golden_path, &trace)?;
+            return Ok(());
+        }
+        let golden = fs::read_to_string(golden_path)
+            .with_context(|| format!("reading {}", golden_path.display()))?;
+        let diff = diff_golden(&trace, &golden, MAX_DIFF_LINES);
+        if !diff.is_empty() {
+            eprint!("{diff}");
+            bail!("trace differs from {}", golden_path.display());
+        }
+    }
+    Ok(())
+}
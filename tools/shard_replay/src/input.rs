@@ -0,0 +1,108 @@
+use std::fs;
+use std::path::Path;
+
+use anyhow::{anyhow, Context};
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+
+use hive_shard_runtime::sensor::{SensorFields, SensorSnapshot};
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, ValueEnum)]
+pub enum InputFormat {
+    Csv,
+    Jsonl,
+}
+
+impl InputFormat {
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()? {
+            "csv" => Some(InputFormat::Csv),
+            "jsonl" | "ndjson" => Some(InputFormat::Jsonl),
+            _ => None,
+        }
+    }
+}
+
+/// One recorded control period.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TimedSnapshot {
+    pub timestamp_ms: i64,
+    #[serde(flatten)]
+    pub snapshot: SensorSnapshot,
+}
+
+/// Flat CSV row; `missing` holds `SensorFields` names separated by `|`.
+#[derive(Debug, Deserialize)]
+struct CsvRow {
+    timestamp_ms: i64,
+    brood_temp_c: i16,
+    brood_humidity_pct: u8,
+    acoustic_surplus_db: i16,
+    daily_mortality_pct: u8,
+    hive_weight_kg_x10: i32,
+    forager_return_delta_pct: i16,
+    varroa_mites_per_100_bees: u8,
+    #[serde(default)]
+    brood_gradient_c: i16,
+    #[serde(default)]
+    missing: String,
+}
+
+impl CsvRow {
+    fn into_record(self) -> anyhow::Result<TimedSnapshot> {
+        let mut missing = SensorFields::empty();
+        for name in self.missing.split('|').map(str::trim).filter(|n| !n.is_empty()) {
+            missing |= SensorFields::from_name(name)
+                .ok_or_else(|| anyhow!("unknown sensor field `{name}`"))?;
+        }
+        Ok(TimedSnapshot {
+            timestamp_ms: self.timestamp_ms,
+            snapshot: SensorSnapshot {
+                brood_temp_c: self.brood_temp_c,
+                brood_humidity_pct: self.brood_humidity_pct,
+                acoustic_surplus_db: self.acoustic_surplus_db,
+                daily_mortality_pct: self.daily_mortality_pct,
+                hive_weight_kg_x10: self.hive_weight_kg_x10,
+                forager_return_delta_pct: self.forager_return_delta_pct,
+                varroa_mites_per_100_bees: self.varroa_mites_per_100_bees,
+                brood_gradient_c: self.brood_gradient_c,
+                missing,
+            },
+        })
+    }
+}
+
+/// Reads a recording, sorted by timestamp; blank JSONL lines are skipped.
+pub fn read_records(path: &Path, format: InputFormat) -> anyhow::Result<Vec<TimedSnapshot>> {
+    let mut records = match format {
+        InputFormat::Csv => {
+            let mut reader = csv::ReaderBuilder::new()
+                .trim(csv::Trim::All)
+                .from_path(path)
+                .with_context(|| format!("opening {}", path.display()))?;
+            reader
+                .deserialize::<CsvRow>()
+                .enumerate()
+                .map(|(i, row)| {
+                    row.with_context(|| format!("{}: record {}", path.display(), i + 1))?
+                        .into_record()
+                })
+                .collect::<anyhow::Result<Vec<_>>>()?
+        }
+        InputFormat::Jsonl => {
+            let contents = fs::read_to_string(path)
+                .with_context(|| format!("reading {}", path.display()))?;
+            contents
+                .lines()
+                .enumerate()
+                .filter(|(_, line)| !line.trim().is_empty())
+                .map(|(i, line)| {
+                    serde_json::from_str(line)
+                        .with_context(|| format!("{}:{}", path.display(), i + 1))
+                })
+                .collect::<anyhow::Result<Vec<_>>>()?
+        }
+    };
+    records.sort_by_key(|r| r.timestamp_ms);
+    Ok(records)
+}
@@ -0,0 +1,3 @@
+pub mod cli;
+pub mod input;
+pub mod trace;
@@ -0,0 +1,6 @@
+fn main() {
+    if let Err(e) = tools_shard_replay::cli::run() {
+        eprintln!("shard-replay error: {e}");
+        std::process::exit(1);
+    }
+}
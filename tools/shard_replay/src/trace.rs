@@ -0,0 +1,142 @@
+use std::fmt::Write as _;
+
+use anyhow::bail;
+use serde::{Deserialize, Serialize};
+
+use hive_shard_runtime::actuator::ActuatorCommandFrame;
+use hive_shard_runtime::band::{BandState, BioloadState};
+use hive_shard_runtime::config::ShardConfig;
+use hive_shard_runtime::controller::NeuromorphicController;
+use hive_shard_runtime::failsafe::FailsafeMode;
+use hive_shard_runtime::sensor::{SensorFields, SensorSnapshot};
+use hive_shard_runtime::HiveShardRuntime;
+
+use crate::input::TimedSnapshot;
+
+/// Runtime state after one replayed step.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TraceRow {
+    pub tick: u64,
+    pub timestamp_ms: i64,
+    pub band: BandState,
+    pub bioload: BioloadState,
+    pub failsafe: FailsafeMode,
+    /// `FailsafeReason::reason_code` of the latest transition.
+    pub failsafe_reason: Option<u8>,
+    pub frame: ActuatorCommandFrame,
+    /// Stepped with every reading missing, for a period absent from the
+    /// recording.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub gap: bool,
+}
+
+/// Controller that never actuates, for replaying the runtime's own logic.
+pub struct NullController;
+
+impl NeuromorphicController for NullController {
+    fn step_neuromorphic(&mut self, _sensors: &SensorSnapshot) -> ActuatorCommandFrame {
+        ActuatorCommandFrame::observation_only()
+    }
+}
+
+/// Steps the runtime once per control period. The clock is anchored to the
+/// first timestamp and advances by the time between records, rounded to
+/// whole periods; periods the recording skipped are stepped with every
+/// reading missing, as the shard would have seen them. A trace depends only
+/// on the recording, the config and the controller.
+pub fn replay<C: NeuromorphicController>(
+    config: ShardConfig,
+    controller: C,
+    records: &[TimedSnapshot],
+) -> anyhow::Result<Vec<TraceRow>> {
+    let period_ms = config.tick_period_ms.max(1) as i64;
+    let mut runtime = HiveShardRuntime::new(config, controller);
+    if let Some(first) = records.first() {
+        runtime.anchor_utc(first.timestamp_ms);
+    }
+    let mut rows = Vec::with_capacity(records.len());
+    let mut previous: Option<&TimedSnapshot> = None;
+    for record in records {
+        if let Some(previous) = previous {
+            let elapsed_ms = record.timestamp_ms - previous.timestamp_ms;
+            let periods = (elapsed_ms + period_ms / 2) / period_ms;
+            if periods == 0 {
+                bail!(
+                    "records at {} and {} ms are less than a control period apart",
+                    previous.timestamp_ms,
+                    record.timestamp_ms
+                );
+            }
+            let mut blank = previous.snapshot.clone();
+            blank.missing = SensorFields::all();
+            for skipped in 1..periods {
+                let timestamp_ms = previous.timestamp_ms + skipped * period_ms;
+                rows.push(step(&mut runtime, timestamp_ms, &blank, true));
+            }
+        }
+        rows.push(step(&mut runtime, record.timestamp_ms, &record.snapshot, false));
+        previous = Some(record);
+    }
+    Ok(rows)
+}
+
+fn step<C: NeuromorphicController>(
+    runtime: &mut HiveShardRuntime<C>,
+    timestamp_ms: i64,
+    snapshot: &SensorSnapshot,
+    gap: bool,
+) -> TraceRow {
+    let frame = runtime.step(snapshot);
+    TraceRow {
+        tick: runtime.tick().ticks(),
+        timestamp_ms,
+        band: runtime.band_state(),
+        bioload: runtime.bioload_state(),
+        failsafe: runtime.failsafe_mode(),
+        failsafe_reason: runtime.failsafe_reason().map(|r| r.reason_code()),
+        frame,
+        gap,
+    }
+}
+
+/// One JSON object per line, newline terminated.
+pub fn to_jsonl(rows: &[TraceRow]) -> serde_json::Result<String> {
+    let mut out = String::new();
+    for row in rows {
+        out.push_str(&serde_json::to_string(row)?);
+        out.push('\n');
+    }
+    Ok(out)
+}
+
+/// Line-by-line comparison against a golden trace; empty when identical.
+pub fn diff_golden(actual: &str, golden: &str, max_lines: usize) -> String {
+    let mut actual_lines = actual.lines();
+    let mut golden_lines = golden.lines();
+    let mut out = String::new();
+    let mut shown = 0;
+    let mut line = 0;
+    loop {
+        line += 1;
+        let (a, g) = (actual_lines.next(), golden_lines.next());
+        if a.is_none() && g.is_none() {
+            break;
+        }
+        if a == g {
+            continue;
+        }
+        if shown == max_lines {
+            let _ = writeln!(out, "... further differences omitted");
+            break;
+        }
+        shown += 1;
+        let _ = writeln!(out, "@@ line {line}");
+        if let Some(g) = g {
+            let _ = writeln!(out, "-{g}");
+        }
+        if let Some(a) = a {
+            let _ = writeln!(out, "+{a}");
+        }
+    }
+    out
+}
@@ -0,0 +1,96 @@
+use std::path::Path;
+use std::process::Command;
+
+use hive_shard_runtime::config::ShardConfig;
+
+use tools_shard_replay::input::{read_records, InputFormat};
+use tools_shard_replay::trace::{diff_golden, replay, to_jsonl, NullController};
+
+const FIXTURES: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures");
+/// Shared with the runtime's own tests so the two cannot drift apart.
+const CONFIG: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/../../crates/hive_shard_runtime/tests/fixtures/shard_config.json"
+);
+
+fn fixture(name: &str) -> String {
+    format!("{FIXTURES}/{name}")
+}
+
+fn config() -> ShardConfig {
+    serde_json::from_str(&std::fs::read_to_string(CONFIG).unwrap()).unwrap()
+}
+
+fn trace() -> String {
+    let records = read_records(Path::new(&fixture("recording.jsonl")), InputFormat::Jsonl).unwrap();
+    to_jsonl(&replay(config(), NullController, &records).unwrap()).unwrap()
+}
+
+#[test]
+fn recording_matches_golden_trace() {
+    let golden = std::fs::read_to_string(fixture("golden.jsonl")).unwrap();
+    assert_eq!(diff_golden(&trace(), &golden, 20), "");
+}
+
+#[test]
+fn gaps_advance_the_clock() {
+    let trace = trace();
+    let rows: Vec<serde_json::Value> = trace
+        .lines()
+        .map(|l| serde_json::from_str(l).unwrap())
+        .collect();
+
+    // Twelve records, with two control periods missing after the fourth.
+    assert_eq!(rows.len(), 14);
+    assert_eq!(rows[4]["gap"], true);
+    assert_eq!(rows[5]["gap"], true);
+    assert_eq!(rows[6]["tick"], 7);
+    assert!(rows[6].get("gap").is_none());
+}
+
+#[test]
+fn diff_golden_reports_changed_and_missing_lines() {
+    let golden = "a\nb\nc\n";
+
+    assert_eq!(
+        diff_golden("a\nx\n", golden, 20),
+        "@@ line 2\n-b\n+x\n@@ line 3\n-c\n"
+    );
+    assert_eq!(
+        diff_golden("x\ny\nz\n", golden, 1),
+        "@@ line 1\n-a\n+x\n... further differences omitted\n"
+    );
+}
+
+#[test]
+fn cli_fails_against_a_stale_golden() {
+    let run = |golden: &str| {
+        Command::new(env!("CARGO_BIN_EXE_shard-replay"))
+            .args(["-c", CONFIG])
+            .args(["-i", &fixture("recording.jsonl")])
+            .arg("-o")
+            .arg(std::env::temp_dir().join("shard_replay_trace.jsonl"))
+            .args(["--golden", golden])
+            .output()
+            .unwrap()
+    };
+
+    assert!(run(&fixture("golden.jsonl")).status.success());
+
+    let stale = std::env::temp_dir().join("shard_replay_stale_golden.jsonl");
+    let golden = std::fs::read_to_string(fixture("golden.jsonl")).unwrap();
+    std::fs::write(&stale, golden.replacen("\"Red\"", "\"Yellow\"", 1)).unwrap();
+    let output = run(stale.to_str().unwrap());
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("trace differs"));
+}
+
+#[test]
+fn records_within_one_period_are_rejected() {
+    let mut records =
+        read_records(Path::new(&fixture("recording.jsonl")), InputFormat::Jsonl).unwrap();
+    records[1].timestamp_ms = records[0].timestamp_ms + 1_000;
+
+    assert!(replay(config(), NullController, &records).is_err());
+}